@@ -0,0 +1,567 @@
+//! A multi-task executor that doesn't require `alloc`.
+//!
+//! Tasks are stored in a fixed number of statically allocated slots, each with a fixed amount of
+//! storage.  Every task has its own waker, so that only tasks that have actually been woken get
+//! polled again.
+
+use core::alloc;
+use core::cell;
+use core::fmt;
+use core::marker;
+use core::mem;
+use core::pin;
+use core::ptr;
+use core::sync::atomic;
+use core::task;
+
+use crate::future::Future;
+
+const FREE: u8 = 0;
+const RESERVED: u8 = 1;
+const OCCUPIED: u8 = 2;
+
+/// The alignment of task storage; futures with a stricter alignment can't be spawned.
+const STORAGE_ALIGN: usize = 8;
+
+/// An executor with `TASKS` task slots, each of which can hold a future of up to `SIZE` bytes.
+///
+/// The executor is meant to be put in a `static`, since tasks (and their wakers) need to live for
+/// as long as the program does:
+///
+/// ```ignore
+/// static EXECUTOR: Executor<8, 1024> = Executor::new();
+///
+/// EXECUTOR.run(|spawner| async move { /* ... */ }, || {
+///     cortex_m::interrupt::free(|_| {
+///         if !EXECUTOR.is_pending() {
+///             cortex_m::asm::wfi();
+///         }
+///     })
+/// })
+/// ```
+pub struct Executor<const TASKS: usize, const SIZE: usize> {
+    running: atomic::AtomicBool,
+    pending: atomic::AtomicBool,
+    main: Header,
+    slots: [Slot<SIZE>; TASKS],
+}
+
+/// A handle that can be used to spawn new tasks onto a running [`Executor`].
+///
+/// Spawners can't be sent to other threads or interrupt handlers, since spawned tasks will be
+/// polled on the thread that is running the executor.
+#[derive(Clone, Copy)]
+pub struct Spawner {
+    pool: &'static dyn Pool,
+    phantom: marker::PhantomData<*mut ()>,
+}
+
+/// The reason why a task could not be spawned.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SpawnError {
+    /// All task slots are currently occupied.
+    Full,
+    /// The task's future is too large (or too strictly aligned) to fit in a task slot.
+    TooLarge,
+}
+
+/// The part of a task that wakers refer to.
+struct Header {
+    woken: atomic::AtomicBool,
+    /// The `pending` flag of the executor that the task belongs to, once it has been started.
+    pending: atomic::AtomicPtr<atomic::AtomicBool>,
+}
+
+struct Slot<const SIZE: usize> {
+    header: Header,
+    state: atomic::AtomicU8,
+    vtable: cell::Cell<Option<VTable>>,
+    storage: cell::UnsafeCell<Storage<SIZE>>,
+}
+
+#[repr(align(8))]
+struct Storage<const SIZE: usize>([mem::MaybeUninit<u8>; SIZE]);
+
+#[derive(Clone, Copy)]
+struct VTable {
+    poll: unsafe fn(*mut u8, &mut task::Context<'_>) -> task::Poll<()>,
+    drop: unsafe fn(*mut u8),
+}
+
+/// The type-erased interface that a [`Spawner`] uses to reach its [`Executor`].
+trait Pool: Sync {
+    fn spawn_raw(
+        &self,
+        size: usize,
+        align: usize,
+        vtable: VTable,
+        write: &mut dyn FnMut(*mut u8),
+    ) -> Result<(), SpawnError>;
+}
+
+// Safety: the storage of a slot is only accessed by whoever moved the slot into the `RESERVED`
+// state (to write a new task), or by the single thread running the executor while the slot is in
+// the `OCCUPIED` state.  Spawners are `!Send`, and are only handed out by the running executor, so
+// all tasks are polled and dropped on that same thread.
+unsafe impl<const TASKS: usize, const SIZE: usize> Sync for Executor<TASKS, SIZE> {}
+
+static WAKER_VTABLE: task::RawWakerVTable =
+    task::RawWakerVTable::new(waker_clone, waker_wake, waker_wake, waker_drop);
+
+impl<const TASKS: usize, const SIZE: usize> Executor<TASKS, SIZE> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const SLOT: Slot<SIZE> = Slot::new();
+
+    /// Creates a new executor with all task slots empty.
+    pub const fn new() -> Self {
+        Executor {
+            running: atomic::AtomicBool::new(false),
+            pending: atomic::AtomicBool::new(false),
+            main: Header::new(),
+            slots: [Self::SLOT; TASKS],
+        }
+    }
+
+    /// Runs the main future created by `init`, as well as any tasks spawned along the way, forever.
+    ///
+    /// The `wait` function is called whenever no tasks are ready to make progress.  It should block
+    /// until an event (such as an interrupt) might have woken a task.  Since a task can be woken
+    /// right before `wait` is called, `wait` must not block if [`is_pending`](Self::is_pending)
+    /// returns `true`, and that check must not race with the event that wakes it up either: on
+    /// Cortex-M, check it with interrupts masked before executing `wfi`, which still wakes up on
+    /// interrupts that become pending while they are masked.
+    ///
+    /// Panics if this executor has already been started.
+    pub fn run<I, F>(&'static self, init: I, mut wait: impl FnMut()) -> !
+    where
+        I: FnOnce(Spawner) -> F,
+        F: Future<Output = ()>,
+    {
+        self.block_on(init, &mut wait);
+        loop {
+            self.pending.store(false, atomic::Ordering::Release);
+            self.poll_tasks();
+            if !self.is_pending() {
+                wait();
+            }
+        }
+    }
+
+    /// Runs the main future created by `init` to completion, polling any spawned tasks while doing
+    /// so, and returns the main future's output.
+    ///
+    /// Spawned tasks that haven't completed by then are left in their slots; since an executor can
+    /// only be started once, they will never be polled again.  See [`run`](Self::run) for the
+    /// requirements on `wait`.
+    ///
+    /// Panics if this executor has already been started.
+    pub fn block_on<I, F>(&'static self, init: I, mut wait: impl FnMut()) -> F::Output
+    where
+        I: FnOnce(Spawner) -> F,
+        F: Future,
+    {
+        if self.running.swap(true, atomic::Ordering::AcqRel) {
+            panic!("the executor has already been started");
+        }
+
+        let pending: *const atomic::AtomicBool = &self.pending;
+        let pending = pending as *mut atomic::AtomicBool;
+        self.main.pending.store(pending, atomic::Ordering::Release);
+        for slot in &self.slots {
+            slot.header
+                .pending
+                .store(pending, atomic::Ordering::Release);
+        }
+
+        let spawner = Spawner {
+            pool: self,
+            phantom: marker::PhantomData,
+        };
+        let mut future = init(spawner);
+        // Safety: the future is shadowed and can't be moved again.
+        let mut future = unsafe { pin::Pin::new_unchecked(&mut future) };
+
+        self.main.woken.store(true, atomic::Ordering::Release);
+        loop {
+            // Any task that is woken from here on sets the flag again, so that it isn't missed by
+            // the check before waiting, no matter which task it is.
+            self.pending.store(false, atomic::Ordering::Release);
+
+            if self.main.woken.swap(false, atomic::Ordering::AcqRel) {
+                let waker = self.main.waker();
+                let mut cx = task::Context::from_waker(&waker);
+                if let task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                    return output;
+                }
+            }
+
+            self.poll_tasks();
+
+            if !self.is_pending() {
+                wait();
+            }
+        }
+    }
+
+    /// Whether a task has been woken (or spawned) since the executor last started polling tasks.
+    ///
+    /// This can be used by the `wait` function of [`run`](Self::run) and
+    /// [`block_on`](Self::block_on) to avoid going to sleep when a task is ready to make progress.
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(atomic::Ordering::Acquire)
+    }
+
+    /// Polls every spawned task that has been woken.
+    fn poll_tasks(&'static self) {
+        for slot in &self.slots {
+            if slot.state.load(atomic::Ordering::Acquire) != OCCUPIED
+                || !slot.header.woken.swap(false, atomic::Ordering::AcqRel)
+            {
+                continue;
+            }
+
+            let vtable = slot
+                .vtable
+                .get()
+                .expect("occupied task slot without a task");
+            let waker = slot.header.waker();
+            let mut cx = task::Context::from_waker(&waker);
+            let storage = slot.storage.get() as *mut u8;
+
+            // Safety: the slot is occupied, so the storage contains a future of the type that the
+            // vtable was created for, and it hasn't been moved since it was written.
+            if let task::Poll::Ready(()) = unsafe { (vtable.poll)(storage, &mut cx) } {
+                unsafe { (vtable.drop)(storage) };
+                slot.vtable.set(None);
+                slot.state.store(FREE, atomic::Ordering::Release);
+            }
+        }
+    }
+}
+
+impl<const TASKS: usize, const SIZE: usize> Default for Executor<TASKS, SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const TASKS: usize, const SIZE: usize> Pool for Executor<TASKS, SIZE> {
+    fn spawn_raw(
+        &self,
+        size: usize,
+        align: usize,
+        vtable: VTable,
+        write: &mut dyn FnMut(*mut u8),
+    ) -> Result<(), SpawnError> {
+        if size > SIZE || align > STORAGE_ALIGN {
+            return Err(SpawnError::TooLarge);
+        }
+
+        let slot = self
+            .slots
+            .iter()
+            .find(|slot| {
+                slot.state
+                    .compare_exchange(
+                        FREE,
+                        RESERVED,
+                        atomic::Ordering::AcqRel,
+                        atomic::Ordering::Relaxed,
+                    )
+                    .is_ok()
+            })
+            .ok_or(SpawnError::Full)?;
+
+        write(slot.storage.get() as *mut u8);
+        slot.vtable.set(Some(vtable));
+        slot.header.woken.store(true, atomic::Ordering::Release);
+        slot.state.store(OCCUPIED, atomic::Ordering::Release);
+        self.pending.store(true, atomic::Ordering::Release);
+
+        Ok(())
+    }
+}
+
+impl<const TASKS: usize, const SIZE: usize> fmt::Debug for Executor<TASKS, SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let occupied = self
+            .slots
+            .iter()
+            .filter(|slot| slot.state.load(atomic::Ordering::Relaxed) != FREE)
+            .count();
+
+        f.debug_struct("Executor")
+            .field("running", &self.running.load(atomic::Ordering::Relaxed))
+            .field("tasks", &occupied)
+            .field("capacity", &TASKS)
+            .field("task_size", &SIZE)
+            .finish()
+    }
+}
+
+impl Spawner {
+    /// Spawns a new task that will run the specified future to completion.
+    pub fn spawn<F>(&self, future: F) -> Result<(), SpawnError>
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        let mut future = Some(future);
+        let layout = alloc::Layout::new::<F>();
+        let vtable = VTable {
+            poll: poll_task::<F>,
+            drop: drop_task::<F>,
+        };
+
+        self.pool
+            .spawn_raw(layout.size(), layout.align(), vtable, &mut |storage| {
+                let future = future.take().expect("task was written more than once");
+                // Safety: the pool checked that the storage is large and aligned enough for `F`.
+                unsafe { ptr::write(storage as *mut F, future) }
+            })
+    }
+}
+
+impl fmt::Debug for Spawner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Spawner").finish()
+    }
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpawnError::Full => f.write_str("all task slots are occupied"),
+            SpawnError::TooLarge => f.write_str("the task does not fit in a task slot"),
+        }
+    }
+}
+
+impl Header {
+    const fn new() -> Self {
+        Header {
+            woken: atomic::AtomicBool::new(false),
+            pending: atomic::AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    fn waker(&'static self) -> task::Waker {
+        let header: *const Header = self;
+        let raw = task::RawWaker::new(header as *const (), &WAKER_VTABLE);
+        // Safety: the waker vtable upholds the `RawWaker` contract for a `'static` header.
+        unsafe { task::Waker::from_raw(raw) }
+    }
+}
+
+impl<const SIZE: usize> Slot<SIZE> {
+    const fn new() -> Self {
+        Slot {
+            header: Header::new(),
+            state: atomic::AtomicU8::new(FREE),
+            vtable: cell::Cell::new(None),
+            storage: cell::UnsafeCell::new(Storage([mem::MaybeUninit::uninit(); SIZE])),
+        }
+    }
+}
+
+unsafe fn poll_task<F>(storage: *mut u8, cx: &mut task::Context<'_>) -> task::Poll<()>
+where
+    F: Future<Output = ()>,
+{
+    pin::Pin::new_unchecked(&mut *(storage as *mut F)).poll(cx)
+}
+
+unsafe fn drop_task<F>(storage: *mut u8) {
+    ptr::drop_in_place(storage as *mut F)
+}
+
+unsafe fn waker_clone(header: *const ()) -> task::RawWaker {
+    task::RawWaker::new(header, &WAKER_VTABLE)
+}
+
+unsafe fn waker_wake(header: *const ()) {
+    let header = &*(header as *const Header);
+    header.woken.store(true, atomic::Ordering::Release);
+    // Safety: the pointer is either null, or points to the `pending` flag of a `'static` executor.
+    if let Some(pending) = header.pending.load(atomic::Ordering::Acquire).as_ref() {
+        pending.store(true, atomic::Ordering::Release);
+    }
+}
+
+unsafe fn waker_drop(_header: *const ()) {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::{Executor, SpawnError};
+    use core::cell;
+    use core::pin;
+    use core::task;
+    use std::boxed::Box;
+    use std::rc::Rc;
+
+    use crate::future::Future;
+
+    /// A task that never completes, but counts how often it is polled and keeps its waker.
+    #[derive(Default)]
+    struct Probe {
+        polls: cell::Cell<usize>,
+        waker: cell::RefCell<Option<task::Waker>>,
+    }
+
+    /// Yields to the executor once, waking itself so that it is polled again right away.
+    struct YieldNow(bool);
+
+    impl Probe {
+        fn task(self: &Rc<Self>) -> impl Future<Output = ()> {
+            let probe = self.clone();
+            core::future::poll_fn(move |cx| {
+                probe.polls.set(probe.polls.get() + 1);
+                *probe.waker.borrow_mut() = Some(cx.waker().clone());
+                task::Poll::Pending
+            })
+        }
+
+        fn wake(&self) {
+            self.waker.borrow().as_ref().unwrap().wake_by_ref();
+        }
+    }
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<()> {
+            if self.0 {
+                task::Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                task::Poll::Pending
+            }
+        }
+    }
+
+    fn executor<const TASKS: usize, const SIZE: usize>() -> &'static Executor<TASKS, SIZE> {
+        Box::leak(Box::new(Executor::new()))
+    }
+
+    fn never_wait() {
+        panic!("the executor waited although a task was ready");
+    }
+
+    #[test]
+    fn returns_the_output_of_the_main_future() {
+        let output = executor::<1, 64>().block_on(|_| async { 42 }, never_wait);
+        assert_eq!(output, 42);
+    }
+
+    #[test]
+    fn only_polls_tasks_that_have_been_woken() {
+        let (a, b) = (Rc::new(Probe::default()), Rc::new(Probe::default()));
+        let (task_a, task_b) = (a.task(), b.task());
+
+        executor::<2, 64>().block_on(
+            move |spawner| async move {
+                spawner.spawn(task_a).unwrap();
+                spawner.spawn(task_b).unwrap();
+                YieldNow(false).await;
+                assert_eq!((a.polls.get(), b.polls.get()), (1, 1));
+
+                a.wake();
+                YieldNow(false).await;
+                assert_eq!((a.polls.get(), b.polls.get()), (2, 1));
+
+                YieldNow(false).await;
+                assert_eq!((a.polls.get(), b.polls.get()), (2, 1));
+            },
+            never_wait,
+        );
+    }
+
+    #[test]
+    fn reuses_slots_of_completed_tasks() {
+        let probe = Rc::new(Probe::default());
+        let task = probe.task();
+
+        executor::<1, 64>().block_on(
+            move |spawner| async move {
+                spawner.spawn(async {}).unwrap();
+                assert_eq!(spawner.spawn(async {}), Err(SpawnError::Full));
+
+                YieldNow(false).await;
+                spawner.spawn(task).unwrap();
+                YieldNow(false).await;
+                assert_eq!(probe.polls.get(), 1);
+            },
+            never_wait,
+        );
+    }
+
+    #[test]
+    fn rejects_tasks_when_all_slots_are_occupied() {
+        let (a, b) = (Rc::new(Probe::default()), Rc::new(Probe::default()));
+        let (task_a, task_b) = (a.task(), b.task());
+
+        executor::<1, 64>().block_on(
+            move |spawner| async move {
+                spawner.spawn(task_a).unwrap();
+                YieldNow(false).await;
+                assert_eq!(spawner.spawn(task_b), Err(SpawnError::Full));
+            },
+            never_wait,
+        );
+    }
+
+    #[test]
+    fn rejects_tasks_that_are_too_large() {
+        let buffer = [0u8; 128];
+
+        let result = executor::<1, 64>().block_on(
+            move |spawner| async move {
+                spawner.spawn(async move {
+                    let _ = buffer;
+                })
+            },
+            never_wait,
+        );
+
+        assert_eq!(result, Err(SpawnError::TooLarge));
+    }
+
+    #[test]
+    fn does_not_wait_after_a_task_was_woken() {
+        let (main, spawned) = (Rc::new(Probe::default()), Rc::new(Probe::default()));
+        let task = spawned.task();
+        let waits = Rc::new(cell::Cell::new(0));
+
+        let executor = executor::<1, 64>();
+        let wait = {
+            let (main, spawned, waits) = (main.clone(), spawned.clone(), waits.clone());
+            move || {
+                assert!(!executor.is_pending());
+                waits.set(waits.get() + 1);
+                // Simulates an interrupt that wakes both tasks while the executor is waiting
+                spawned.wake();
+                main.wake();
+            }
+        };
+
+        executor.block_on(
+            move |spawner| async move {
+                spawner.spawn(task).unwrap();
+                core::future::poll_fn(|cx| {
+                    *main.waker.borrow_mut() = Some(cx.waker().clone());
+                    if spawned.polls.get() < 3 {
+                        task::Poll::Pending
+                    } else {
+                        task::Poll::Ready(())
+                    }
+                })
+                .await
+            },
+            wait,
+        );
+
+        assert_eq!(waits.get(), 3);
+    }
+}
@@ -40,9 +40,34 @@ impl<T: ops::Generator<Yield = ()>> Future for GenFuture<T> {
     }
 }
 
+#[cfg(target_arch = "arm")]
 static CONTEXT: bare_metal::Mutex<cell::Cell<ContextHolder>> =
     bare_metal::Mutex::new(cell::Cell::new(ContextHolder(None)));
 
+// Hosted targets (e.g. simulation platforms) don't have interrupts to guard against, but might
+// run several executors on different threads.
+#[cfg(not(target_arch = "arm"))]
+#[thread_local]
+static CONTEXT: cell::Cell<ContextHolder> = cell::Cell::new(ContextHolder(None));
+
+/// Runs the provided closure with exclusive access to the stored task context.
+#[cfg(target_arch = "arm")]
+fn with_context<F, R>(f: F) -> R
+where
+    F: FnOnce(&cell::Cell<ContextHolder>) -> R,
+{
+    cortex_m::interrupt::free(|cs| f(CONTEXT.borrow(cs)))
+}
+
+/// Runs the provided closure with exclusive access to the stored task context.
+#[cfg(not(target_arch = "arm"))]
+fn with_context<F, R>(f: F) -> R
+where
+    F: FnOnce(&cell::Cell<ContextHolder>) -> R,
+{
+    f(&CONTEXT)
+}
+
 #[derive(Clone, Copy)]
 struct ContextHolder(Option<ptr::NonNull<task::Context<'static>>>);
 
@@ -52,9 +77,7 @@ struct SetOnDrop(ContextHolder);
 
 impl Drop for SetOnDrop {
     fn drop(&mut self) {
-        cortex_m::interrupt::free(|cs| {
-            CONTEXT.borrow(cs).set(ContextHolder((self.0).0.take()));
-        });
+        with_context(|context| context.set(ContextHolder((self.0).0.take())));
     }
 }
 
@@ -67,11 +90,8 @@ where
     // transmute the context's lifetime to 'static so we can store it.
     let cx =
         unsafe { core::mem::transmute::<&mut task::Context<'_>, &mut task::Context<'static>>(cx) };
-    let old_cx = cortex_m::interrupt::free(|cs| {
-        CONTEXT
-            .borrow(cs)
-            .replace(ContextHolder(Some(ptr::NonNull::from(cx))))
-    });
+    let old_cx =
+        with_context(|context| context.replace(ContextHolder(Some(ptr::NonNull::from(cx)))));
     let _reset = SetOnDrop(old_cx);
     f()
 }
@@ -89,7 +109,7 @@ where
 {
     // Clear the entry so that nested `get_task_waker` calls
     // will fail or set their own value.
-    let cx_ptr = cortex_m::interrupt::free(|cs| CONTEXT.borrow(cs).replace(ContextHolder(None)));
+    let cx_ptr = with_context(|context| context.replace(ContextHolder(None)));
     let _reset = SetOnDrop(cx_ptr);
 
     let mut cx_ptr = cx_ptr.0.expect(
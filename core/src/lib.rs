@@ -11,6 +11,8 @@
 )]
 #![feature(thread_local, generator_trait, optin_builtin_traits)]
 
+pub mod executor;
 pub mod future;
+pub mod registry;
 
 pub use core::*;
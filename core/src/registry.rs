@@ -0,0 +1,284 @@
+//! Runtime bookkeeping of which peripherals have been claimed, and by whom.
+//!
+//! Platforms hand out peripherals by value, and can only hand out each of them once.  The
+//! [`Registry`] keeps track of who has claimed what, so that claiming something twice results in
+//! an [`AlreadyTaken`] error that names both claimants, rather than a generic panic.  Claims are
+//! recorded in a fixed amount of space, and running out of it is a [`ClaimError::Full`] error.
+
+use core::fmt;
+
+/// A peripheral resource that can be claimed from a platform.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Resource {
+    /// A GPIO pin, identified by its port and pin number.
+    Pin { port: u8, pin: u8 },
+    /// A hardware timer.
+    Timer(u8),
+    /// A UART peripheral.
+    Uart(u8),
+    /// An I²C bus peripheral.
+    I2c(u8),
+    /// An SPI bus peripheral.
+    Spi(u8),
+}
+
+/// An error returned when claiming a resource that has already been claimed by someone else.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AlreadyTaken {
+    resource: Resource,
+    holder: &'static str,
+    claimant: &'static str,
+}
+
+/// An error returned when a resource can't be claimed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ClaimError {
+    /// The resource has already been claimed by someone else.
+    AlreadyTaken(AlreadyTaken),
+    /// The registry has no room left to record another claim.
+    Full {
+        /// The resource that was being claimed.
+        resource: Resource,
+        /// Who tried to claim the resource.
+        claimant: &'static str,
+    },
+}
+
+/// Keeps track of up to `N` claimed resources at a time.
+#[derive(Clone, Debug)]
+pub struct Registry<const N: usize> {
+    claims: [Option<Claim>; N],
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Claim {
+    resource: Resource,
+    claimant: &'static str,
+}
+
+impl Resource {
+    /// The GPIO pin with the specified port and pin number.
+    pub const fn pin(port: u8, pin: u8) -> Self {
+        Resource::Pin { port, pin }
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Resource::Pin { port, pin } => write!(f, "pin {}.{:02}", port, pin),
+            Resource::Timer(n) => write!(f, "timer {}", n),
+            Resource::Uart(n) => write!(f, "uart {}", n),
+            Resource::I2c(n) => write!(f, "i2c {}", n),
+            Resource::Spi(n) => write!(f, "spi {}", n),
+        }
+    }
+}
+
+impl AlreadyTaken {
+    /// The resource that was already taken.
+    pub fn resource(&self) -> Resource {
+        self.resource
+    }
+
+    /// Who is currently holding the resource.
+    pub fn holder(&self) -> &'static str {
+        self.holder
+    }
+
+    /// Who tried to claim the resource.
+    pub fn claimant(&self) -> &'static str {
+        self.claimant
+    }
+}
+
+impl ClaimError {
+    /// The resource that couldn't be claimed.
+    pub fn resource(&self) -> Resource {
+        match *self {
+            ClaimError::AlreadyTaken(ref err) => err.resource,
+            ClaimError::Full { resource, .. } => resource,
+        }
+    }
+
+    /// Who tried to claim the resource.
+    pub fn claimant(&self) -> &'static str {
+        match *self {
+            ClaimError::AlreadyTaken(ref err) => err.claimant,
+            ClaimError::Full { claimant, .. } => claimant,
+        }
+    }
+}
+
+impl fmt::Display for ClaimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ClaimError::AlreadyTaken(ref err) => err.fmt(f),
+            ClaimError::Full { resource, claimant } => write!(
+                f,
+                "{} can't be taken by {}, because the resource registry is full",
+                resource, claimant
+            ),
+        }
+    }
+}
+
+impl From<AlreadyTaken> for ClaimError {
+    fn from(err: AlreadyTaken) -> Self {
+        ClaimError::AlreadyTaken(err)
+    }
+}
+
+impl fmt::Display for AlreadyTaken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is already taken by {}, so it can't be taken by {}",
+            self.resource, self.holder, self.claimant
+        )
+    }
+}
+
+impl<const N: usize> Registry<N> {
+    /// Creates a new registry where all resources are free.
+    pub const fn new() -> Self {
+        Registry { claims: [None; N] }
+    }
+
+    /// Claims the specified resource on behalf of `claimant`.
+    ///
+    /// Fails if the resource is already claimed, or if `N` resources are already claimed.
+    pub fn claim(&mut self, resource: Resource, claimant: &'static str) -> Result<(), ClaimError> {
+        if let Some(holder) = self.holder(resource) {
+            return Err(ClaimError::AlreadyTaken(AlreadyTaken {
+                resource,
+                holder,
+                claimant,
+            }));
+        }
+
+        let slot = self
+            .claims
+            .iter_mut()
+            .find(|claim| claim.is_none())
+            .ok_or(ClaimError::Full { resource, claimant })?;
+        *slot = Some(Claim { resource, claimant });
+
+        Ok(())
+    }
+
+    /// Releases the specified resource so that it can be claimed again, returning who was holding
+    /// it (if anyone).
+    pub fn release(&mut self, resource: Resource) -> Option<&'static str> {
+        let slot = self
+            .claims
+            .iter_mut()
+            .find(|claim| matches!(claim, Some(claim) if claim.resource == resource))?;
+        slot.take().map(|claim| claim.claimant)
+    }
+
+    /// Who is currently holding the specified resource, if anyone.
+    pub fn holder(&self, resource: Resource) -> Option<&'static str> {
+        self.claims()
+            .find(|&(claimed, _)| claimed == resource)
+            .map(|(_, claimant)| claimant)
+    }
+
+    /// Whether the specified resource is free to be claimed.
+    pub fn is_free(&self, resource: Resource) -> bool {
+        self.holder(resource).is_none()
+    }
+
+    /// All currently claimed resources, along with who is holding them.
+    pub fn claims(&self) -> impl Iterator<Item = (Resource, &'static str)> + '_ {
+        self.claims
+            .iter()
+            .flatten()
+            .map(|claim| (claim.resource, claim.claimant))
+    }
+}
+
+impl<const N: usize> Default for Registry<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClaimError, Registry, Resource};
+
+    const D7: Resource = Resource::pin(0, 7);
+    const TIMER: Resource = Resource::Timer(0);
+
+    #[test]
+    fn names_both_claimants_when_already_taken() {
+        let mut registry = Registry::<4>::new();
+        registry.claim(D7, "main_led").unwrap();
+
+        match registry.claim(D7, "d7") {
+            Err(ClaimError::AlreadyTaken(err)) => {
+                assert_eq!(err.resource(), D7);
+                assert_eq!(err.holder(), "main_led");
+                assert_eq!(err.claimant(), "d7");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!(registry.holder(D7), Some("main_led"));
+    }
+
+    #[test]
+    fn releases_to_be_claimed_again() {
+        let mut registry = Registry::<4>::new();
+        registry.claim(D7, "main_led").unwrap();
+
+        assert_eq!(registry.release(D7), Some("main_led"));
+        assert_eq!(registry.release(D7), None);
+        assert!(registry.is_free(D7));
+
+        registry.claim(D7, "d7").unwrap();
+        assert_eq!(registry.holder(D7), Some("d7"));
+    }
+
+    #[test]
+    fn lists_current_claims() {
+        let mut registry = Registry::<4>::new();
+        registry.claim(D7, "main_led").unwrap();
+        registry.claim(TIMER, "timer0").unwrap();
+        registry.release(D7);
+        registry.claim(Resource::Uart(0), "uart").unwrap();
+
+        let mut claims = [None; 4];
+        for (claim, slot) in registry.claims().zip(&mut claims) {
+            *slot = Some(claim);
+        }
+        assert_eq!(
+            claims,
+            [
+                Some((Resource::Uart(0), "uart")),
+                Some((TIMER, "timer0")),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn fails_when_full() {
+        let mut registry = Registry::<1>::new();
+        registry.claim(D7, "main_led").unwrap();
+
+        let err = registry.claim(TIMER, "timer0").unwrap_err();
+        assert_eq!(
+            err,
+            ClaimError::Full {
+                resource: TIMER,
+                claimant: "timer0"
+            }
+        );
+        assert_eq!((err.resource(), err.claimant()), (TIMER, "timer0"));
+
+        registry.release(D7);
+        registry.claim(TIMER, "timer0").unwrap();
+    }
+}
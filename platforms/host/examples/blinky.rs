@@ -0,0 +1,59 @@
+//! A simple example app that blinks the main LED of any feather board.
+//!
+//! It is wired up to use a simulated feather, and prints the state of the main LED every time it
+//! changes.
+#![deny(
+    missing_debug_implementations,
+    missing_copy_implementations,
+    trivial_casts,
+    trivial_numeric_casts,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications,
+    clippy::all
+)]
+#![forbid(unsafe_code)]
+
+use embedded_platform::prelude::*;
+use futures::prelude::*;
+
+fn main() -> ! {
+    host_platform::SimulatedFeather::main(|mut platform| async {
+        let probe = platform.probe();
+        std::thread::spawn(move || {
+            let mut seen = 0;
+            loop {
+                let history = probe.history(host_platform::pins::MAIN_LED);
+                for on in &history[seen..] {
+                    println!("main LED is {}", if *on { "on" } else { "off" });
+                }
+                seen = history.len();
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+        });
+
+        let timer = platform.take_timer0().into_periodic_timer(1.0.hz())?;
+        feather_blink(platform, timer).await?;
+        Ok(())
+    })
+}
+
+async fn feather_blink<P>(
+    mut feather: P,
+    mut timer: impl embedded_platform::timer::Timer<Error = P::Error> + Unpin,
+) -> Result<(), P::Error>
+where
+    P: embedded_platform::specs::feather::Feather,
+{
+    let main_led = feather.take_main_led().into_push_pull_output_pin(false)?;
+    let mut main_led = embedded_platform::gpio::Stateful::new(main_led, false);
+
+    timer.start().await?;
+    let mut ticks = timer.ticks();
+
+    while ticks.try_next().await?.is_some() {
+        main_led.toggle().await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+//! The shared state of a simulated board, and a [`Probe`] for inspecting and driving it.
+use crate::gpio;
+use std::collections;
+use std::sync;
+use std::task;
+
+/// The complete simulated state of a board.
+///
+/// Peripherals handed out by the platform and any [`Probe`]s share this state.
+#[derive(Debug)]
+pub(crate) struct Board {
+    pub pins: Vec<PinState>,
+    pub uart: UartState,
+    pub i2c: collections::BTreeMap<u8, I2cDevice>,
+}
+
+#[derive(Debug)]
+pub(crate) struct PinState {
+    pub mode: gpio::Mode,
+    pub external: Option<bool>,
+    pub latch: bool,
+    pub history: Vec<bool>,
+    pub waker: Option<task::Waker>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct UartState {
+    pub rx: collections::VecDeque<u8>,
+    pub tx: Vec<u8>,
+    pub rx_waker: Option<task::Waker>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct I2cDevice {
+    pub rx: collections::VecDeque<u8>,
+    pub tx: Vec<u8>,
+}
+
+pub(crate) type SharedBoard = sync::Arc<sync::Mutex<Board>>;
+
+impl Board {
+    pub(crate) fn new(pin_count: usize) -> SharedBoard {
+        let pins = (0..pin_count).map(|_| PinState::new()).collect();
+        let uart = UartState::default();
+        let i2c = collections::BTreeMap::new();
+
+        sync::Arc::new(sync::Mutex::new(Board { pins, uart, i2c }))
+    }
+}
+
+impl PinState {
+    fn new() -> Self {
+        let mode = gpio::Mode::FloatingInput;
+        let external = None;
+        let latch = false;
+        let history = Vec::new();
+        let waker = None;
+
+        PinState {
+            mode,
+            external,
+            latch,
+            history,
+            waker,
+        }
+    }
+
+    /// The level that would be observed by reading this pin.
+    pub(crate) fn level(&self) -> bool {
+        match self.mode {
+            gpio::Mode::PushPullOutput => self.latch,
+            gpio::Mode::OpenDrainOutput => self.latch && self.external.unwrap_or(true),
+            gpio::Mode::PullUpInput => self.external.unwrap_or(true),
+            gpio::Mode::PullDownInput | gpio::Mode::FloatingInput => self.external.unwrap_or(false),
+            // The input buffer of a disabled pin is disconnected, so it always reads low
+            gpio::Mode::Disabled => false,
+        }
+    }
+
+    /// Wakes up the task that is waiting for an edge on this pin, if any.
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A handle to a simulated board that can be used to drive inputs and inspect outputs.
+///
+/// A probe stays connected to the board even after the platform has been moved into the
+/// application, so test code can interact with peripherals while the application is running.
+#[derive(Clone, Debug)]
+pub struct Probe {
+    board: SharedBoard,
+}
+
+impl Probe {
+    pub(crate) fn new(board: SharedBoard) -> Self {
+        Probe { board }
+    }
+
+    /// Externally drives the specified pin to a high or low level.
+    pub fn drive(&self, pin: usize, high: bool) {
+        let mut board = self.board.lock().unwrap();
+        board.pins[pin].external = Some(high);
+        board.pins[pin].wake();
+    }
+
+    /// Stops externally driving the specified pin, letting it float or be pulled.
+    pub fn release(&self, pin: usize) {
+        let mut board = self.board.lock().unwrap();
+        board.pins[pin].external = None;
+        board.pins[pin].wake();
+    }
+
+    /// The current level of the specified pin, as it would be read by the application.
+    pub fn level(&self, pin: usize) -> bool {
+        self.board.lock().unwrap().pins[pin].level()
+    }
+
+    /// The mode that the application has currently configured the specified pin to be in.
+    pub fn mode(&self, pin: usize) -> gpio::Mode {
+        self.board.lock().unwrap().pins[pin].mode
+    }
+
+    /// All of the levels that the application has written to the specified pin, in order.
+    pub fn history(&self, pin: usize) -> Vec<bool> {
+        self.board.lock().unwrap().pins[pin].history.clone()
+    }
+
+    /// Sends bytes to the UART, so that they can be read by the application.
+    pub fn uart_send(&self, bytes: &[u8]) {
+        let mut board = self.board.lock().unwrap();
+        board.uart.rx.extend(bytes);
+        if let Some(waker) = board.uart.rx_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Takes all bytes that the application has written to the UART so far.
+    pub fn uart_receive(&self) -> Vec<u8> {
+        let mut board = self.board.lock().unwrap();
+        std::mem::take(&mut board.uart.tx)
+    }
+
+    /// Attaches a device to the I²C bus at the specified address, if there isn't one already.
+    pub fn i2c_attach(&self, address: u8) {
+        self.board.lock().unwrap().i2c.entry(address).or_default();
+    }
+
+    /// Queues up bytes that the device at the specified address will respond with when read.
+    ///
+    /// The device is attached to the bus if it wasn't already.
+    pub fn i2c_respond(&self, address: u8, bytes: &[u8]) {
+        self.board
+            .lock()
+            .unwrap()
+            .i2c
+            .entry(address)
+            .or_default()
+            .rx
+            .extend(bytes);
+    }
+
+    /// Takes all bytes that the application has written to the device at the specified address.
+    pub fn i2c_written(&self, address: u8) -> Vec<u8> {
+        self.board
+            .lock()
+            .unwrap()
+            .i2c
+            .get_mut(&address)
+            .map(|device| std::mem::take(&mut device.tx))
+            .unwrap_or_default()
+    }
+}
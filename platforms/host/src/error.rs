@@ -0,0 +1,64 @@
+use crate::gpio;
+use embedded_platform::io;
+use embedded_platform::registry;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Error {
+    Eof,
+    WriteZero,
+    /// An operation did not complete in time.
+    TimedOut,
+    /// A pin was used in a way that its current mode doesn't allow.
+    InvalidMode(gpio::Mode),
+    /// No device responded on the I²C bus at the specified address.
+    NoDevice(u8),
+    /// A timer was ticked before it was started.
+    TimerNotStarted,
+    /// A timer was configured with a rate that isn't a positive, finite number of hertz.
+    InvalidRate,
+    /// A resource couldn't be claimed, usually because it was already taken by someone else.
+    Claim(registry::ClaimError),
+}
+
+impl io::IoError for Error {
+    fn kind(&self) -> io::ErrorKind {
+        match *self {
+            Error::Eof => io::ErrorKind::UnexpectedEof,
+            Error::WriteZero => io::ErrorKind::WriteZero,
+            Error::TimedOut => io::ErrorKind::TimedOut,
+            Error::NoDevice(_) => io::ErrorKind::Nack,
+            Error::Claim(_) => io::ErrorKind::Busy,
+            Error::InvalidMode(_) | Error::TimerNotStarted | Error::InvalidRate => {
+                io::ErrorKind::Other
+            }
+        }
+    }
+
+    fn timed_out() -> Self {
+        Error::TimedOut
+    }
+}
+
+impl io::ReadError for Error {
+    fn eof() -> Self {
+        Error::Eof
+    }
+}
+
+impl io::WriteError for Error {
+    fn write_zero() -> Self {
+        Error::WriteZero
+    }
+}
+
+impl From<registry::ClaimError> for Error {
+    fn from(err: registry::ClaimError) -> Self {
+        Error::Claim(err)
+    }
+}
+
+impl From<Error> for io::ErrorKind {
+    fn from(err: Error) -> Self {
+        io::IoError::kind(&err)
+    }
+}
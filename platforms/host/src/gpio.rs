@@ -0,0 +1,210 @@
+use crate::board;
+use crate::error;
+use core::fmt;
+use core::pin;
+use core::task;
+
+pub use embedded_platform::gpio::Mode;
+
+/// A simulated GPIO pin.
+///
+/// Unlike pins on real hardware, the mode of a simulated pin is tracked at runtime, so the same
+/// type is used for all modes, and every pin is a [`FlexPin`](embedded_platform::gpio::FlexPin).
+/// Writing to a pin that is not in an output mode, or reading from or waiting for an edge on a
+/// disabled pin, results in an [`error::Error::InvalidMode`] error.
+pub struct Pin {
+    index: usize,
+    board: board::SharedBoard,
+    last: Option<bool>,
+}
+
+impl Pin {
+    pub(crate) fn new(index: usize, board: board::SharedBoard) -> Self {
+        let last = None;
+        Pin { index, board, last }
+    }
+
+    /// The index of this pin on the simulated board.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub(crate) fn board(&self) -> &board::SharedBoard {
+        &self.board
+    }
+
+    fn into_mode(mut self, mode: Mode, initial_high: Option<bool>) -> Self {
+        self.switch_mode(mode, initial_high);
+        self
+    }
+
+    fn switch_mode(&mut self, mode: Mode, initial_high: Option<bool>) {
+        {
+            let mut board = self.board.lock().unwrap();
+            let state = &mut board.pins[self.index];
+            state.mode = mode;
+            if let Some(high) = initial_high {
+                state.latch = high;
+                state.history.push(high);
+            }
+        }
+        // The level may be different in the new mode, so start detecting edges from scratch
+        self.last = None;
+    }
+}
+
+impl embedded_platform::gpio::Pin for Pin {
+    type Error = error::Error;
+}
+
+impl embedded_platform::gpio::InputPin for Pin {
+    fn poll_get(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        let this = self.get_mut();
+        let board = this.board.lock().unwrap();
+        let state = &board.pins[this.index];
+        if state.mode == Mode::Disabled {
+            return task::Poll::Ready(Err(error::Error::InvalidMode(state.mode)));
+        }
+        let level = state.level();
+        this.last = Some(level);
+        task::Poll::Ready(Ok(level))
+    }
+}
+
+/// Edges are detected by comparing levels, so pulses that are shorter than the time between two
+/// polls are not noticed.
+impl embedded_platform::gpio::EdgeInputPin for Pin {
+    fn poll_wait_for_edge(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        edge: embedded_platform::gpio::Edge,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        let this = self.get_mut();
+        let mut board = this.board.lock().unwrap();
+        let state = &mut board.pins[this.index];
+        if state.mode == Mode::Disabled {
+            return task::Poll::Ready(Err(error::Error::InvalidMode(state.mode)));
+        }
+        let level = state.level();
+
+        if let Some(last) = this.last.replace(level) {
+            if last != level && edge.matches(level) {
+                return task::Poll::Ready(Ok(level));
+            }
+        }
+
+        state.waker = Some(cx.waker().clone());
+        task::Poll::Pending
+    }
+}
+
+impl embedded_platform::gpio::OutputPin for Pin {
+    fn poll_set(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        high: bool,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let mut board = self.board.lock().unwrap();
+        let state = &mut board.pins[self.index];
+        match state.mode {
+            Mode::OpenDrainOutput | Mode::PushPullOutput => {
+                state.latch = high;
+                state.history.push(high);
+                task::Poll::Ready(Ok(()))
+            }
+            mode => task::Poll::Ready(Err(error::Error::InvalidMode(mode))),
+        }
+    }
+}
+
+impl embedded_platform::gpio::StatefulOutputPin for Pin {
+    fn poll_is_set_high(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        let board = self.board.lock().unwrap();
+        let state = &board.pins[self.index];
+        match state.mode {
+            Mode::OpenDrainOutput | Mode::PushPullOutput => task::Poll::Ready(Ok(state.latch)),
+            mode => task::Poll::Ready(Err(error::Error::InvalidMode(mode))),
+        }
+    }
+}
+
+impl embedded_platform::gpio::FlexPin for Pin {
+    fn mode(&self) -> Mode {
+        self.board.lock().unwrap().pins[self.index].mode
+    }
+
+    fn set_mode(&mut self, mode: Mode, initial_high: bool) -> Result<(), Self::Error> {
+        let initial_high = match mode {
+            Mode::OpenDrainOutput | Mode::PushPullOutput => Some(initial_high),
+            Mode::Disabled | Mode::FloatingInput | Mode::PullUpInput | Mode::PullDownInput => None,
+        };
+        self.switch_mode(mode, initial_high);
+        Ok(())
+    }
+}
+
+impl embedded_platform::gpio::IntoFlexPin for Pin {
+    type FlexPin = Self;
+
+    fn into_flex_pin(self) -> Result<Self::FlexPin, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl embedded_platform::gpio::IntoFloatingInputPin for Pin {
+    type FloatingInputPin = Self;
+
+    fn into_floating_input_pin(self) -> Result<Self::FloatingInputPin, Self::Error> {
+        Ok(self.into_mode(Mode::FloatingInput, None))
+    }
+}
+
+impl embedded_platform::gpio::IntoPullUpInputPin for Pin {
+    type PullUpInputPin = Self;
+
+    fn into_pull_up_input_pin(self) -> Result<Self::PullUpInputPin, Self::Error> {
+        Ok(self.into_mode(Mode::PullUpInput, None))
+    }
+}
+
+impl embedded_platform::gpio::IntoPullDownInputPin for Pin {
+    type PullDownInputPin = Self;
+
+    fn into_pull_down_input_pin(self) -> Result<Self::PullDownInputPin, Self::Error> {
+        Ok(self.into_mode(Mode::PullDownInput, None))
+    }
+}
+
+impl embedded_platform::gpio::IntoOpenDrainOutputPin for Pin {
+    type OpenDrainOutputPin = Self;
+
+    fn into_open_drain_output_pin(
+        self,
+        initial_high: bool,
+    ) -> Result<Self::OpenDrainOutputPin, Self::Error> {
+        Ok(self.into_mode(Mode::OpenDrainOutput, Some(initial_high)))
+    }
+}
+
+impl embedded_platform::gpio::IntoPushPullOutputPin for Pin {
+    type PushPullOutputPin = Self;
+
+    fn into_push_pull_output_pin(
+        self,
+        initial_high: bool,
+    ) -> Result<Self::PushPullOutputPin, Self::Error> {
+        Ok(self.into_mode(Mode::PushPullOutput, Some(initial_high)))
+    }
+}
+
+impl fmt::Debug for Pin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pin").field("index", &self.index).finish()
+    }
+}
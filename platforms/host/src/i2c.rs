@@ -0,0 +1,166 @@
+use crate::board;
+use crate::error;
+use crate::gpio;
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// A simulated I²C bus.
+///
+/// Devices on the bus are simulated using a [`board::Probe`].
+pub struct I2c {
+    board: board::SharedBoard,
+}
+
+/// An ongoing read from a simulated I²C device.
+pub struct I2cRead {
+    board: board::SharedBoard,
+    address: u8,
+}
+
+/// An ongoing write to a simulated I²C device.
+pub struct I2cWrite {
+    board: board::SharedBoard,
+    address: u8,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct I2cMapping;
+
+impl I2c {
+    pub(crate) fn new(board: board::SharedBoard) -> Self {
+        I2c { board }
+    }
+
+    fn check_device(&self, address: u8) -> Result<(), error::Error> {
+        if self.board.lock().unwrap().i2c.contains_key(&address) {
+            Ok(())
+        } else {
+            Err(error::Error::NoDevice(address))
+        }
+    }
+}
+
+impl embedded_platform::i2c::I2cBusMapping<gpio::Pin, gpio::Pin> for I2cMapping {
+    type Error = error::Error;
+    type Bus = I2c;
+
+    fn poll_initialize(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        sda: &mut gpio::Pin,
+        _scl: &mut gpio::Pin,
+    ) -> task::Poll<Result<Self::Bus, Self::Error>>
+    where
+        Self: Sized,
+    {
+        task::Poll::Ready(Ok(I2c::new(sda.board().clone())))
+    }
+}
+
+impl embedded_platform::i2c::I2cRead for I2c {
+    type Error = error::Error;
+    type Read = I2cRead;
+
+    fn poll_begin_read(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        address: u8,
+    ) -> task::Poll<Result<Self::Read, Self::Error>> {
+        self.check_device(address)?;
+        let board = self.board.clone();
+        task::Poll::Ready(Ok(I2cRead { board, address }))
+    }
+}
+
+impl embedded_platform::io::Read for I2cRead {
+    type Error = error::Error;
+
+    fn poll_read(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let mut board = self.board.lock().unwrap();
+        let device = board
+            .i2c
+            .get_mut(&self.address)
+            .ok_or(error::Error::NoDevice(self.address))?;
+
+        let len = buffer.len().min(device.rx.len());
+        for (dest, byte) in buffer.iter_mut().zip(device.rx.drain(..len)) {
+            *dest = byte;
+        }
+        task::Poll::Ready(Ok(len))
+    }
+}
+
+impl embedded_platform::i2c::I2cWrite for I2c {
+    type Error = error::Error;
+    type Write = I2cWrite;
+
+    fn poll_begin_write(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        address: u8,
+    ) -> task::Poll<Result<Self::Write, Self::Error>> {
+        self.check_device(address)?;
+        let board = self.board.clone();
+        task::Poll::Ready(Ok(I2cWrite { board, address }))
+    }
+}
+
+impl embedded_platform::io::Write for I2cWrite {
+    type Error = error::Error;
+
+    fn poll_write(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let mut board = self.board.lock().unwrap();
+        let device = board
+            .i2c
+            .get_mut(&self.address)
+            .ok_or(error::Error::NoDevice(self.address))?;
+
+        device.tx.extend_from_slice(bytes);
+        task::Poll::Ready(Ok(bytes.len()))
+    }
+
+    fn poll_flush(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl fmt::Debug for I2c {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("I2c").finish()
+    }
+}
+
+impl fmt::Debug for I2cRead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("I2cRead")
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+impl fmt::Debug for I2cWrite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("I2cWrite")
+            .field("address", &self.address)
+            .finish()
+    }
+}
@@ -0,0 +1,339 @@
+//! A simulated platform that runs on the host machine.
+//!
+//! This makes it possible to run and test generic code written against `embedded-platform` traits
+//! without any hardware.  All peripherals are simulated in software, and a [`board::Probe`] can be
+//! used to drive inputs and inspect outputs while the application is running.
+#![deny(
+    // missing_docs,
+    missing_debug_implementations,
+    missing_copy_implementations,
+    trivial_casts,
+    trivial_numeric_casts,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications,
+    clippy::all
+)]
+#![forbid(unsafe_code)]
+
+use core::future;
+use core::task;
+use embedded_platform::executor;
+use embedded_platform::platform;
+use embedded_platform::platform::recovery;
+use embedded_platform::registry;
+use std::thread;
+use std::time;
+
+pub mod board;
+pub mod error;
+pub mod gpio;
+pub mod i2c;
+pub mod serial;
+pub mod timer;
+
+/// Indices of the pins of a [`SimulatedFeather`], for use with a [`board::Probe`].
+pub mod pins {
+    pub const SDA: usize = 0;
+    pub const SCL: usize = 1;
+    pub const D2: usize = 2;
+    pub const D3: usize = 3;
+    pub const D4: usize = 4;
+    pub const D5: usize = 5;
+    pub const D6: usize = 6;
+    pub const D7: usize = 7;
+    pub const D8: usize = 8;
+    pub const P0: usize = 9;
+    pub const TX: usize = 10;
+    pub const RX: usize = 11;
+    pub const MISO: usize = 12;
+    pub const MOSI: usize = 13;
+    pub const SCK: usize = 14;
+    pub const A5: usize = 15;
+    pub const A4: usize = 16;
+    pub const A3: usize = 17;
+    pub const A2: usize = 18;
+    pub const A1: usize = 19;
+    pub const A0: usize = 20;
+    pub const MAIN_LED: usize = 21;
+
+    pub(crate) const COUNT: usize = 22;
+}
+
+/// The maximum number of tasks that can be spawned at the same time.
+pub const MAX_TASKS: usize = 8;
+/// The maximum size of the future of a spawned task, in bytes.
+pub const MAX_TASK_SIZE: usize = 1024;
+
+/// The number of timers of a [`SimulatedFeather`].
+const TIMERS: usize = 5;
+/// The number of resources that can be taken from a [`SimulatedFeather`]: all pins, the timers, and
+/// the UART.
+pub const RESOURCES: usize = pins::COUNT + TIMERS + 1;
+
+/// A simulated board that conforms to the Adafruit Feather specification.
+///
+/// The main LED is connected to a dedicated pin, [`pins::MAIN_LED`].
+#[derive(Debug)]
+pub struct SimulatedFeather {
+    board: board::SharedBoard,
+    registry: registry::Registry<RESOURCES>,
+    spawner: Option<executor::Spawner>,
+}
+
+impl SimulatedFeather {
+    /// Runs an application to completion against a freshly initialized simulated board.
+    ///
+    /// This is like [`platform::Platform::main`], except that it returns the result of the
+    /// application instead of exiting the process, which makes it suitable for tests.
+    pub fn run<I, F>(run: I) -> Result<(), error::Error>
+    where
+        I: FnOnce(Self) -> F,
+        F: future::Future<Output = Result<(), error::Error>>,
+    {
+        Self::block_on(|spawner| async move {
+            use embedded_platform::platform::PlatformExt;
+            let mut platform = SimulatedFeather::initialize().await?;
+            platform.spawner = Some(spawner);
+            run(platform).await
+        })
+    }
+
+    /// Runs an application against a freshly initialized simulated board, letting `handler` decide
+    /// what happens if it fails.
+    ///
+    /// Every restart initializes a fresh simulated board, so probes have to be created again.  It
+    /// also runs on a fresh executor, so tasks spawned by a failed attempt are never polled again.
+    /// Returns `Ok(())` once the application completes, or `Err` with the recovery that the handler
+    /// chose if it wasn't a restart.
+    pub fn run_with<I, F, H>(mut run: I, mut handler: H) -> Result<(), recovery::Recovery>
+    where
+        I: FnMut(Self) -> F,
+        F: future::Future<Output = Result<(), error::Error>>,
+        H: recovery::Handler<error::Error>,
+    {
+        let mut number = 1;
+        loop {
+            let result = Self::block_on(|spawner| {
+                let prepare = move |platform: &mut Self| platform.spawner = Some(spawner);
+                recovery::attempt(number, &mut run, &mut handler, prepare)
+            });
+            match result {
+                Err(recovery::Recovery::Restart) => number += 1,
+                result => return result,
+            }
+        }
+    }
+
+    fn block_on<I, F>(init: I) -> F::Output
+    where
+        I: FnOnce(executor::Spawner) -> F,
+        F: future::Future,
+    {
+        // Every run gets its own executor, so that several simulations can run in parallel (e.g. in
+        // tests).  Executors must be `'static`, so this leaks a little bit of memory per run.
+        let executor: &'static executor::Executor<MAX_TASKS, MAX_TASK_SIZE> =
+            Box::leak(Box::new(executor::Executor::new()));
+
+        // Peripherals might be woken from other threads, so don't sleep for too long at a time.
+        let wait = || thread::park_timeout(time::Duration::from_millis(1));
+        executor.block_on(init, wait)
+    }
+
+    /// Creates a new probe connected to this board.
+    pub fn probe(&self) -> board::Probe {
+        board::Probe::new(self.board.clone())
+    }
+
+    pub fn take_timer0(&mut self) -> timer::Timer {
+        self.try_take_timer0().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn take_timer1(&mut self) -> timer::Timer {
+        self.try_take_timer1().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn take_timer2(&mut self) -> timer::Timer {
+        self.try_take_timer2().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn take_timer3(&mut self) -> timer::Timer {
+        self.try_take_timer3().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn take_timer4(&mut self) -> timer::Timer {
+        self.try_take_timer4().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_take_timer0(&mut self) -> Result<timer::Timer, registry::ClaimError> {
+        self.try_take_timer(0, "timer0")
+    }
+
+    pub fn try_take_timer1(&mut self) -> Result<timer::Timer, registry::ClaimError> {
+        self.try_take_timer(1, "timer1")
+    }
+
+    pub fn try_take_timer2(&mut self) -> Result<timer::Timer, registry::ClaimError> {
+        self.try_take_timer(2, "timer2")
+    }
+
+    pub fn try_take_timer3(&mut self) -> Result<timer::Timer, registry::ClaimError> {
+        self.try_take_timer(3, "timer3")
+    }
+
+    pub fn try_take_timer4(&mut self) -> Result<timer::Timer, registry::ClaimError> {
+        self.try_take_timer(4, "timer4")
+    }
+
+    /// Hands back a timer that was previously taken, so that it can be taken again.
+    pub fn release_timer(&mut self, timer: timer::Timer) {
+        self.registry
+            .release(registry::Resource::Timer(timer.index()));
+    }
+
+    pub fn take_uart(&mut self) -> serial::Uart {
+        self.try_take_uart().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_take_uart(&mut self) -> Result<serial::Uart, registry::ClaimError> {
+        self.registry.claim(registry::Resource::Uart(0), "uart")?;
+        Ok(serial::Uart::new(self.board.clone()))
+    }
+
+    /// Hands back the UART, so that it can be taken again.
+    pub fn release_uart(&mut self, uart: serial::Uart) {
+        drop(uart);
+        self.registry.release(registry::Resource::Uart(0));
+    }
+
+    /// The registry of all resources that have been taken from this board, and by whom.
+    pub fn registry(&self) -> &registry::Registry<RESOURCES> {
+        &self.registry
+    }
+
+    /// Takes the main I²C bus, which also claims the `SDA` and `SCL` pins.
+    ///
+    /// The bus is usually taken through the [`Feather`](embedded_platform::specs::feather::Feather)
+    /// trait, but this makes it available to platforms that wrap this one as well.
+    pub fn try_take_main_i2c_bus(&mut self) -> Result<i2c::I2c, registry::ClaimError> {
+        let sda = self.try_take_pin(pins::SDA, "main_i2c")?;
+        if let Err(e) = self.try_take_pin(pins::SCL, "main_i2c") {
+            self.release_pin(sda);
+            return Err(e);
+        }
+        Ok(i2c::I2c::new(self.board.clone()))
+    }
+
+    fn try_take_timer(
+        &mut self,
+        index: u8,
+        name: &'static str,
+    ) -> Result<timer::Timer, registry::ClaimError> {
+        self.registry
+            .claim(registry::Resource::Timer(index), name)?;
+        Ok(timer::Timer::new(index))
+    }
+
+    /// Takes the pin with the specified index in [`pins`], on behalf of `name`.
+    ///
+    /// Pins are usually taken through the [`Feather`](embedded_platform::specs::feather::Feather)
+    /// trait, but this makes it possible for platforms that wrap this one to map them differently.
+    pub fn try_take_pin(
+        &mut self,
+        index: usize,
+        name: &'static str,
+    ) -> Result<gpio::Pin, registry::ClaimError> {
+        self.registry.claim(pin_resource(index), name)?;
+        Ok(gpio::Pin::new(index, self.board.clone()))
+    }
+
+    /// Hands back a pin that was previously taken, so that it can be taken again.
+    pub fn release_pin(&mut self, pin: gpio::Pin) {
+        self.registry.release(pin_resource(pin.index()));
+    }
+}
+
+fn pin_resource(index: usize) -> registry::Resource {
+    registry::Resource::pin(0, index as u8)
+}
+
+impl platform::Platform for SimulatedFeather {
+    type Error = error::Error;
+
+    fn main_with<I, F, H>(mut run: I, mut handler: H) -> !
+    where
+        I: FnMut(Self) -> F,
+        F: future::Future<Output = Result<(), Self::Error>>,
+        H: recovery::Handler<Self::Error>,
+    {
+        loop {
+            let handler = |failure| handler.handle(failure);
+            match Self::run_with(&mut run, handler) {
+                Ok(()) => std::process::exit(0),
+                // Simulate a system reset by starting over from scratch.
+                Err(recovery::Recovery::Reset) => continue,
+                Err(_) => std::process::exit(1),
+            }
+        }
+    }
+
+    fn poll_initialize(_cx: &mut task::Context<'_>) -> task::Poll<Result<Self, Self::Error>> {
+        let board = board::Board::new(pins::COUNT);
+        let registry = registry::Registry::new();
+        let spawner = None;
+
+        task::Poll::Ready(Ok(Self {
+            board,
+            registry,
+            spawner,
+        }))
+    }
+
+    fn spawner(&self) -> executor::Spawner {
+        self.spawner
+            .expect("the platform was initialized outside of SimulatedFeather::run")
+    }
+}
+
+macro_rules! take_pin {
+    ($platform:ident, $port:ident::$pin:ident, $claimant:expr) => {
+        $platform.try_take_pin($port::$pin, $claimant)
+    };
+}
+
+macro_rules! release_pin {
+    ($platform:ident, $port:ident::$pin:ident, $value:expr) => {
+        $platform.release_pin($value)
+    };
+}
+
+embedded_platform::feather_board! {
+    impl Feather for SimulatedFeather {
+        take = take_pin;
+        release = release_pin;
+        main_led = pins::MAIN_LED: gpio::Pin;
+        main_i2c: i2c::I2cMapping => try_take_main_i2c_bus;
+
+        sda = pins::SDA: gpio::Pin;
+        scl = pins::SCL: gpio::Pin;
+        d2 = pins::D2: gpio::Pin;
+        d3 = pins::D3: gpio::Pin;
+        d4 = pins::D4: gpio::Pin;
+        d5 = pins::D5: gpio::Pin;
+        d6 = pins::D6: gpio::Pin;
+        d7 = pins::D7: gpio::Pin;
+        d8 = pins::D8: gpio::Pin;
+        p0 = pins::P0: gpio::Pin;
+        tx = pins::TX: gpio::Pin;
+        rx = pins::RX: gpio::Pin;
+        miso = pins::MISO: gpio::Pin;
+        mosi = pins::MOSI: gpio::Pin;
+        sck = pins::SCK: gpio::Pin;
+        a5 = pins::A5: gpio::Pin;
+        a4 = pins::A4: gpio::Pin;
+        a3 = pins::A3: gpio::Pin;
+        a2 = pins::A2: gpio::Pin;
+        a1 = pins::A1: gpio::Pin;
+        a0 = pins::A0: gpio::Pin;
+    }
+}
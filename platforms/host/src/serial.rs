@@ -0,0 +1,79 @@
+use crate::board;
+use crate::error;
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// A simulated UART.
+///
+/// Bytes written by the application can be inspected with [`board::Probe::uart_receive`], and
+/// bytes can be sent to the application with [`board::Probe::uart_send`].
+pub struct Uart {
+    board: board::SharedBoard,
+}
+
+impl Uart {
+    pub(crate) fn new(board: board::SharedBoard) -> Self {
+        Uart { board }
+    }
+}
+
+impl embedded_platform::io::Read for Uart {
+    type Error = error::Error;
+
+    fn poll_read(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let mut board = self.board.lock().unwrap();
+        let uart = &mut board.uart;
+        if uart.rx.is_empty() && !buffer.is_empty() {
+            uart.rx_waker = Some(cx.waker().clone());
+            return task::Poll::Pending;
+        }
+
+        let len = buffer.len().min(uart.rx.len());
+        for (dest, byte) in buffer.iter_mut().zip(uart.rx.drain(..len)) {
+            *dest = byte;
+        }
+        task::Poll::Ready(Ok(len))
+    }
+}
+
+impl embedded_platform::io::Write for Uart {
+    type Error = error::Error;
+
+    fn poll_write(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        self.board.lock().unwrap().uart.tx.extend_from_slice(bytes);
+        task::Poll::Ready(Ok(bytes.len()))
+    }
+
+    fn poll_flush(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl embedded_platform::serial::SerialRead for Uart {}
+
+impl embedded_platform::serial::SerialWrite for Uart {}
+
+impl fmt::Debug for Uart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Uart").finish()
+    }
+}
@@ -0,0 +1,119 @@
+use crate::error;
+use core::pin;
+use core::task;
+use std::thread;
+use std::time;
+
+/// A simulated timer that runs on the host's wall clock.
+#[allow(missing_copy_implementations)]
+#[derive(Debug)]
+pub struct Timer {
+    index: u8,
+    mode: Mode,
+    deadline: Option<time::Instant>,
+    armed: Option<time::Instant>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Mode {
+    Oneshot(time::Duration),
+    Periodic(time::Duration),
+}
+
+impl Timer {
+    pub(crate) fn new(index: u8) -> Self {
+        let mode = Mode::Oneshot(time::Duration::from_micros(1));
+        let deadline = None;
+        let armed = None;
+        Timer {
+            index,
+            mode,
+            deadline,
+            armed,
+        }
+    }
+
+    /// The index of this timer on the board it was taken from.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    fn period(&self) -> time::Duration {
+        match self.mode {
+            Mode::Oneshot(period) | Mode::Periodic(period) => period,
+        }
+    }
+}
+
+impl embedded_platform::timer::Timer for Timer {
+    type Error = error::Error;
+
+    fn poll_start(
+        mut self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = &mut *self;
+        this.deadline = Some(time::Instant::now() + this.period());
+        this.armed = None;
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_tick(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = &mut *self;
+        let deadline = this.deadline.ok_or(error::Error::TimerNotStarted)?;
+        let now = time::Instant::now();
+
+        if now >= deadline {
+            this.deadline = match this.mode {
+                Mode::Oneshot(_) => None,
+                Mode::Periodic(period) => Some(deadline + period),
+            };
+            this.armed = None;
+            task::Poll::Ready(Ok(()))
+        } else {
+            // Only spawn one sleeper per deadline; the waker is cheap to clone but threads aren't.
+            if this.armed != Some(deadline) {
+                let waker = cx.waker().clone();
+                thread::spawn(move || {
+                    thread::sleep(deadline - now);
+                    waker.wake();
+                });
+                this.armed = Some(deadline);
+            }
+            task::Poll::Pending
+        }
+    }
+}
+
+impl embedded_platform::timer::IntoPeriodicTimer for Timer {
+    type PeriodicTimer = Self;
+
+    fn into_periodic_timer(
+        self,
+        rate: embedded_platform::time::Rate,
+    ) -> Result<Self::PeriodicTimer, Self::Error> {
+        let period = time::Duration::try_from_secs_f32(1.0 / rate.as_hz())
+            .ok()
+            .filter(|period| *period > time::Duration::ZERO)
+            .ok_or(error::Error::InvalidRate)?;
+        let mode = Mode::Periodic(period);
+        Ok(Timer { mode, ..self })
+    }
+}
+
+impl embedded_platform::timer::IntoOneshotTimer for Timer {
+    type OneshotTimer = Self;
+
+    fn into_oneshot_timer(
+        self,
+        delay: embedded_platform::time::Duration,
+    ) -> Result<Self::OneshotTimer, Self::Error> {
+        // `Duration` counts microseconds; see `Duration::from_millis`.
+        let period = time::Duration::from_micros(u64::from(delay.as_nanos()));
+        let mode = Mode::Oneshot(period);
+        Ok(Timer { mode, ..self })
+    }
+}
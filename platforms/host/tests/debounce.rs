@@ -0,0 +1,200 @@
+use core::task;
+use embedded_platform::gpio::Button;
+use embedded_platform::gpio::ButtonEvent;
+use embedded_platform::gpio::Debounced;
+use embedded_platform::prelude::*;
+use embedded_platform::specs::feather::Feather;
+use embedded_platform::time::Duration;
+use futures::prelude::*;
+use host_platform::board::Probe;
+use host_platform::pins;
+use host_platform::SimulatedFeather;
+use std::thread;
+use std::time;
+
+/// Drives `D2` to each level in turn, holding it there for the specified number of milliseconds.
+fn drive_d2(probe: Probe, levels: &'static [(bool, u64)]) {
+    thread::spawn(move || {
+        for &(high, millis) in levels {
+            probe.drive(pins::D2, high);
+            thread::sleep(time::Duration::from_millis(millis));
+        }
+    });
+}
+
+#[test]
+fn collapses_bounces_into_one_change() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let d2 = platform.take_d2().into_pull_down_input_pin()?;
+        let mut d2 = Debounced::new(d2, platform.take_timer1(), Duration::from_millis(30))?;
+        assert!(!d2.get().await?);
+
+        drive_d2(
+            probe,
+            &[
+                (true, 5),
+                (false, 5),
+                (true, 5),
+                (false, 5),
+                (true, 100),
+                (false, 5),
+                (true, 5),
+                (false, 100),
+            ],
+        );
+
+        // Every bounce would otherwise show up as a separate change
+        let changes = d2.changes().take(2).try_collect::<Vec<_>>().await?;
+        assert_eq!(changes, vec![true, false]);
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn first_read_waits_for_the_pin_to_settle() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let d2 = platform.take_d2().into_pull_down_input_pin()?;
+        let mut d2 = Debounced::new(d2, platform.take_timer1(), Duration::from_millis(30))?;
+
+        let start = time::Instant::now();
+        drive_d2(
+            probe,
+            &[(true, 5), (false, 5), (true, 5), (false, 5), (true, 0)],
+        );
+
+        assert!(d2.get().await?);
+        assert!(start.elapsed() >= time::Duration::from_millis(50));
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn ignores_bounces_back_to_the_old_level() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let d2 = platform.take_d2().into_pull_down_input_pin()?;
+        let mut d2 = Debounced::new(d2, platform.take_timer1(), Duration::from_millis(30))?;
+        assert!(!d2.get().await?);
+
+        let start = time::Instant::now();
+        drive_d2(probe, &[(true, 5), (false, 100), (true, 0)]);
+
+        // The short pulse isn't reported, only the level change after it
+        assert!(d2.wait_for_edge(embedded_platform::gpio::Edge::Any).await?);
+        assert!(start.elapsed() >= time::Duration::from_millis(105));
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn reads_do_not_swallow_edges() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let d2 = platform.take_d2().into_pull_down_input_pin()?;
+        let mut d2 = Debounced::new(d2, platform.take_timer1(), Duration::from_millis(20))?;
+        let mut timer = platform.take_timer2().into_periodic_timer(100.0.hz())?;
+        assert!(!d2.get().await?);
+
+        // Let the edge API see the low level before the pin changes
+        assert!(futures::poll!(d2.wait_for_rising_edge()).is_pending());
+        probe.drive(pins::D2, true);
+
+        timer.start().await?;
+        let mut ticks = timer.ticks();
+        while !d2.get().await? {
+            ticks.try_next().await?;
+        }
+
+        // The edge has already happened, so it's reported right away
+        let poll = futures::poll!(d2.wait_for_rising_edge());
+        assert!(matches!(poll, task::Poll::Ready(Ok(true))));
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn reports_a_click_after_the_double_click_window() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let d2 = platform.take_d2().into_pull_down_input_pin()?;
+        let d2 = Debounced::new(d2, platform.take_timer1(), Duration::from_millis(10))?;
+        let mut button = Button::new(
+            d2,
+            platform.take_timer2(),
+            Duration::from_millis(300),
+            Duration::from_millis(100),
+        )?;
+
+        let start = time::Instant::now();
+        drive_d2(probe, &[(false, 20), (true, 40), (false, 0)]);
+
+        assert_eq!(button.try_next().await?, Some(ButtonEvent::Click));
+        assert!(start.elapsed() >= time::Duration::from_millis(160));
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn reports_a_double_click_without_a_click() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let d2 = platform.take_d2().into_pull_down_input_pin()?;
+        let d2 = Debounced::new(d2, platform.take_timer1(), Duration::from_millis(10))?;
+        let mut button = Button::new(
+            d2,
+            platform.take_timer2(),
+            Duration::from_millis(300),
+            Duration::from_millis(100),
+        )?;
+
+        drive_d2(
+            probe,
+            &[
+                (false, 20),
+                (true, 40),
+                (false, 40),
+                (true, 40),
+                (false, 200),
+                (true, 500),
+                (false, 0),
+            ],
+        );
+
+        // A click from the first press would be reported before the long press
+        assert_eq!(button.try_next().await?, Some(ButtonEvent::DoubleClick));
+        assert_eq!(button.try_next().await?, Some(ButtonEvent::LongPress));
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn reports_a_long_press_while_held() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let d2 = platform.take_d2().into_pull_down_input_pin()?;
+        let d2 = Debounced::new(d2, platform.take_timer1(), Duration::from_millis(10))?;
+        let mut button = Button::new(
+            d2,
+            platform.take_timer2(),
+            Duration::from_millis(300),
+            Duration::from_millis(100),
+        )?;
+
+        let start = time::Instant::now();
+        drive_d2(probe.clone(), &[(false, 20), (true, 1000), (false, 0)]);
+
+        assert_eq!(button.try_next().await?, Some(ButtonEvent::LongPress));
+        assert!(start.elapsed() >= time::Duration::from_millis(320));
+        assert!(probe.level(pins::D2));
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,164 @@
+use core::task;
+use embedded_platform::executor;
+use embedded_platform::platform;
+use embedded_platform::platform::recovery;
+use embedded_platform::prelude::*;
+use embedded_platform::registry;
+use embedded_platform::specs::feather::Feather;
+use futures::prelude::*;
+use host_platform::error;
+use host_platform::gpio;
+use host_platform::i2c;
+use host_platform::pins;
+use host_platform::SimulatedFeather;
+
+/// A simulated board whose main LED is wired to `D7`, and lit by driving that pin low.
+#[derive(Debug)]
+struct ActiveLowFeather(SimulatedFeather);
+
+impl ActiveLowFeather {
+    fn try_take_main_i2c_bus(&mut self) -> Result<i2c::I2c, registry::ClaimError> {
+        self.0.try_take_main_i2c_bus()
+    }
+}
+
+impl platform::Platform for ActiveLowFeather {
+    type Error = error::Error;
+
+    fn main_with<I, F, H>(mut run: I, handler: H) -> !
+    where
+        I: FnMut(Self) -> F,
+        F: Future<Output = Result<(), Self::Error>>,
+        H: recovery::Handler<Self::Error>,
+    {
+        SimulatedFeather::main_with(move |platform| run(ActiveLowFeather(platform)), handler)
+    }
+
+    fn poll_initialize(cx: &mut task::Context<'_>) -> task::Poll<Result<Self, Self::Error>> {
+        SimulatedFeather::poll_initialize(cx).map_ok(ActiveLowFeather)
+    }
+
+    fn spawner(&self) -> executor::Spawner {
+        self.0.spawner()
+    }
+}
+
+macro_rules! take_pin {
+    ($platform:ident, $port:ident::$pin:ident, $claimant:expr) => {
+        $platform.0.try_take_pin($port::$pin, $claimant)
+    };
+}
+
+macro_rules! release_pin {
+    ($platform:ident, $port:ident::$pin:ident, $value:expr) => {
+        $platform.0.release_pin($value)
+    };
+}
+
+embedded_platform::feather_board! {
+    impl Feather for ActiveLowFeather {
+        take = take_pin;
+        release = release_pin;
+        main_led => d7 as active_low;
+        main_i2c: i2c::I2cMapping => try_take_main_i2c_bus;
+
+        sda = pins::SDA: gpio::Pin;
+        scl = pins::SCL: gpio::Pin;
+        d2 = pins::D2: gpio::Pin;
+        d3 = pins::D3: gpio::Pin;
+        d4 = pins::D4: gpio::Pin;
+        d5 = pins::D5: gpio::Pin;
+        d6 = pins::D6: gpio::Pin;
+        d7 = pins::D7: gpio::Pin;
+        d8 = pins::D8: gpio::Pin;
+        p0 = pins::P0: gpio::Pin;
+        tx = pins::TX: gpio::Pin;
+        rx = pins::RX: gpio::Pin;
+        miso = pins::MISO: gpio::Pin;
+        mosi = pins::MOSI: gpio::Pin;
+        sck = pins::SCK: gpio::Pin;
+        a5 = pins::A5: gpio::Pin;
+        a4 = pins::A4: gpio::Pin;
+        a3 = pins::A3: gpio::Pin;
+        a2 = pins::A2: gpio::Pin;
+        a1 = pins::A1: gpio::Pin;
+        a0 = pins::A0: gpio::Pin;
+    }
+}
+
+async fn feather_blink<P>(
+    mut feather: P,
+    mut timer: impl embedded_platform::timer::Timer<Error = P::Error> + Unpin,
+    blinks: usize,
+) -> Result<(), P::Error>
+where
+    P: Feather,
+{
+    let main_led = feather.take_main_led().into_push_pull_output_pin(false)?;
+    let mut main_led = embedded_platform::gpio::Stateful::new(main_led, false);
+
+    timer.start().await?;
+    let mut ticks = timer.ticks().take(blinks);
+
+    while ticks.try_next().await?.is_some() {
+        main_led.toggle().await?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn blinks_main_led() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let timer = platform.take_timer0().into_periodic_timer(1000.0.hz())?;
+
+        feather_blink(platform, timer, 4).await?;
+
+        assert_eq!(
+            probe.history(pins::MAIN_LED),
+            vec![false, true, false, true, false]
+        );
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn blinks_active_low_main_led() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let timer = platform.take_timer0().into_periodic_timer(1000.0.hz())?;
+
+        feather_blink(ActiveLowFeather(platform), timer, 4).await?;
+
+        // The LED is turned on by driving its pin low
+        assert_eq!(
+            probe.history(pins::D7),
+            vec![true, false, true, false, true]
+        );
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn names_both_claimants_of_a_pin() {
+    SimulatedFeather::run(|platform| async move {
+        let mut feather = ActiveLowFeather(platform);
+        let main_led = feather.take_main_led();
+
+        match feather.try_take_d7() {
+            Err(registry::ClaimError::AlreadyTaken(err)) => {
+                assert_eq!(err.holder(), "main_led");
+                assert_eq!(err.claimant(), "d7");
+            }
+            result => panic!("unexpected result {:?}", result.map(|_| ())),
+        }
+
+        feather.release_main_led(main_led);
+        feather.try_take_d7()?;
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,71 @@
+use embedded_platform::gpio::FlexPin;
+use embedded_platform::prelude::*;
+use embedded_platform::specs::feather::Feather;
+use futures::prelude::*;
+use host_platform::error;
+use host_platform::gpio::Mode;
+use host_platform::pins;
+use host_platform::SimulatedFeather;
+use std::thread;
+use std::time;
+
+#[test]
+fn waits_for_edges_driven_by_probe() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let mut d2 = platform.take_d2().into_pull_down_input_pin()?;
+        assert!(!d2.get().await?);
+
+        thread::spawn(move || {
+            for &high in &[true, false, true, false] {
+                thread::sleep(time::Duration::from_millis(20));
+                probe.drive(pins::D2, high);
+            }
+        });
+
+        assert!(d2.wait_for_rising_edge().await?);
+        assert!(!d2.wait_for_falling_edge().await?);
+
+        let changes = d2.changes().take(2).try_collect::<Vec<_>>().await?;
+        assert_eq!(changes, vec![true, false]);
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn ignores_edges_of_the_wrong_direction() {
+    SimulatedFeather::run(|mut platform| async move {
+        let probe = platform.probe();
+        let mut d3 = platform.take_d3().into_pull_up_input_pin()?;
+        assert!(d3.get().await?);
+
+        thread::spawn(move || {
+            for &high in &[false, true] {
+                thread::sleep(time::Duration::from_millis(20));
+                probe.drive(pins::D3, high);
+            }
+        });
+
+        // The falling edge comes first, but only the rising one is reported
+        assert!(d3.wait_for_rising_edge().await?);
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn rejects_waiting_for_edges_on_disabled_pins() {
+    SimulatedFeather::run(|mut platform| async move {
+        let mut d4 = platform.take_d4().into_flex_pin()?;
+        d4.set_mode(Mode::Disabled, false)?;
+
+        let result = d4.wait_for_rising_edge().await;
+        assert!(matches!(
+            result,
+            Err(error::Error::InvalidMode(Mode::Disabled))
+        ));
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,69 @@
+use embedded_platform::platform::recovery;
+use embedded_platform::platform::Platform;
+use futures::future;
+use futures::task;
+use host_platform::error;
+use host_platform::SimulatedFeather;
+use std::cell;
+use std::rc::Rc;
+
+/// Yields to the executor once, so that other tasks get a chance to run.
+async fn yield_now() {
+    let mut yielded = false;
+    future::poll_fn(|cx| {
+        if yielded {
+            task::Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            task::Poll::Pending
+        }
+    })
+    .await
+}
+
+#[test]
+fn restarts_on_a_fresh_executor() {
+    let attempts = host_platform::MAX_TASKS + 2;
+    let attempt = Rc::new(cell::Cell::new(0));
+    let polls = Rc::new(cell::Cell::new(0));
+
+    let app = |platform: SimulatedFeather| {
+        let (attempt, polls) = (attempt.clone(), polls.clone());
+        async move {
+            attempt.set(attempt.get() + 1);
+
+            // Stale tasks of earlier attempts must neither be polled nor take up task slots
+            let polled = polls.get();
+            for _ in 0..10 {
+                yield_now().await;
+            }
+            assert_eq!(polls.get(), polled);
+
+            let task = async move {
+                loop {
+                    polls.set(polls.get() + 1);
+                    yield_now().await;
+                }
+            };
+            platform
+                .spawner()
+                .spawn(task)
+                .expect("spawning a task failed");
+
+            if attempt.get() < attempts {
+                Err(error::Error::TimedOut)
+            } else {
+                Ok(())
+            }
+        }
+    };
+    let handler = |failure: recovery::Failure<error::Error>| {
+        assert_eq!(failure.stage(), recovery::Stage::Run);
+        future::ready(recovery::Recovery::Restart)
+    };
+
+    SimulatedFeather::run_with(app, handler).unwrap();
+
+    assert_eq!(attempt.get(), attempts);
+}
@@ -0,0 +1,23 @@
+use embedded_platform::prelude::*;
+use host_platform::error;
+use host_platform::SimulatedFeather;
+
+#[test]
+fn rejects_invalid_rates() {
+    SimulatedFeather::run(|mut platform| async move {
+        let timers = vec![
+            platform.take_timer0(),
+            platform.take_timer1(),
+            platform.take_timer2(),
+            platform.take_timer3(),
+        ];
+        let rates = [0.0, -1.0, f32::NAN, f32::INFINITY];
+
+        for (timer, &hz) in timers.into_iter().zip(&rates) {
+            let result = timer.into_periodic_timer(hz.hz());
+            assert!(matches!(result, Err(error::Error::InvalidRate)));
+        }
+        Ok(())
+    })
+    .unwrap();
+}
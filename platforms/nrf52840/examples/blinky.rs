@@ -38,15 +38,14 @@ async fn feather_blink<P>(
 where
     P: embedded_platform::specs::feather::Feather,
 {
-    let mut main_led = feather.take_main_led().into_push_pull_output_pin(false)?;
-    let mut on = false;
+    let main_led = feather.take_main_led().into_push_pull_output_pin(false)?;
+    let mut main_led = embedded_platform::gpio::Stateful::new(main_led, false);
 
     timer.start().await?;
     let mut ticks = timer.ticks();
 
     while let Some(_) = ticks.try_next().await? {
-        on = !on;
-        main_led.set(on).await?;
+        main_led.toggle().await?;
     }
 
     Ok(())
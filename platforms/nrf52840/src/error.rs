@@ -1,23 +1,58 @@
+use embedded_platform::io;
+
 #[derive(Debug)]
 pub enum Error {
+    AlreadyInitialized,
+    /// A resource couldn't be claimed, usually because it was already taken by someone else.
+    Claim(embedded_platform::registry::ClaimError),
     Eof,
     WriteZero,
+    TimedOut,
+    /// A pin was used in a way that its current mode doesn't allow.
+    InvalidMode(embedded_platform::gpio::Mode),
+    /// The operation isn't supported by this platform yet.
+    Unsupported,
     Uarte(nrf52840_hal::uarte::Error),
     Spim(nrf52840_hal::spim::Error),
 }
 
-impl embedded_platform::io::ReadError for Error {
+impl io::IoError for Error {
+    fn kind(&self) -> io::ErrorKind {
+        match *self {
+            Error::Eof => io::ErrorKind::UnexpectedEof,
+            Error::WriteZero => io::ErrorKind::WriteZero,
+            Error::TimedOut => io::ErrorKind::TimedOut,
+            Error::AlreadyInitialized | Error::Claim(_) => io::ErrorKind::Busy,
+            Error::Uarte(nrf52840_hal::uarte::Error::Timeout(_)) => io::ErrorKind::TimedOut,
+            Error::InvalidMode(_) | Error::Unsupported => io::ErrorKind::Other,
+            // The UARTE and SPIM drivers don't report why a transfer failed, only that it did
+            Error::Uarte(_) | Error::Spim(_) => io::ErrorKind::Other,
+        }
+    }
+
+    fn timed_out() -> Self {
+        Error::TimedOut
+    }
+}
+
+impl io::ReadError for Error {
     fn eof() -> Self {
         Error::Eof
     }
 }
 
-impl embedded_platform::io::WriteError for Error {
+impl io::WriteError for Error {
     fn write_zero() -> Self {
         Error::WriteZero
     }
 }
 
+impl From<embedded_platform::registry::ClaimError> for Error {
+    fn from(err: embedded_platform::registry::ClaimError) -> Self {
+        Error::Claim(err)
+    }
+}
+
 impl From<nrf52840_hal::uarte::Error> for Error {
     fn from(err: nrf52840_hal::uarte::Error) -> Self {
         Error::Uarte(err)
@@ -29,3 +64,9 @@ impl From<nrf52840_hal::spim::Error> for Error {
         Error::Spim(err)
     }
 }
+
+impl From<Error> for io::ErrorKind {
+    fn from(err: Error) -> Self {
+        io::IoError::kind(&err)
+    }
+}
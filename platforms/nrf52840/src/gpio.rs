@@ -11,8 +11,246 @@ pub struct Pin<P>(pub(crate) P)
 where
     P: Unpin + ?Sized;
 
+/// A pin whose mode is chosen at runtime, created by turning any pin into a
+/// [`FlexPin`](embedded_platform::gpio::FlexPin).
+///
+/// All pins share this type, no matter which port they are on, so they can be stored in a single
+/// table.
+pub struct AnyPin {
+    state: Option<State>,
+}
+
+enum State {
+    Disabled(gpio::Pin<gpio::Disconnected>),
+    FloatingInput(gpio::Pin<gpio::Input<gpio::Floating>>),
+    PullUpInput(gpio::Pin<gpio::Input<gpio::PullUp>>),
+    PullDownInput(gpio::Pin<gpio::Input<gpio::PullDown>>),
+    OpenDrainOutput(gpio::Pin<gpio::Output<gpio::OpenDrain>>),
+    PushPullOutput(gpio::Pin<gpio::Output<gpio::PushPull>>),
+}
+
+impl AnyPin {
+    /// The port that this pin is on.
+    pub fn port(&self) -> u8 {
+        match self.with_pin(|pin| pin.port()) {
+            gpio::Port::Port0 => 0,
+            gpio::Port::Port1 => 1,
+        }
+    }
+
+    /// The index of this pin within its port.
+    pub fn index(&self) -> u8 {
+        self.with_pin(|pin| pin.pin())
+    }
+
+    fn state(&self) -> &State {
+        // The state is only ever missing in the middle of `set_mode`
+        self.state.as_ref().unwrap()
+    }
+
+    fn with_pin<R>(&self, f: impl FnOnce(&dyn PinInfo) -> R) -> R {
+        match self.state() {
+            State::Disabled(pin) => f(pin),
+            State::FloatingInput(pin) => f(pin),
+            State::PullUpInput(pin) => f(pin),
+            State::PullDownInput(pin) => f(pin),
+            State::OpenDrainOutput(pin) => f(pin),
+            State::PushPullOutput(pin) => f(pin),
+        }
+    }
+}
+
+/// The parts of a HAL pin that don't depend on its mode.
+trait PinInfo {
+    fn port(&self) -> gpio::Port;
+    fn pin(&self) -> u8;
+}
+
+impl<M> PinInfo for gpio::Pin<M> {
+    fn port(&self) -> gpio::Port {
+        gpio::Pin::port(self)
+    }
+
+    fn pin(&self) -> u8 {
+        gpio::Pin::pin(self)
+    }
+}
+
+impl State {
+    fn new<M>(pin: gpio::Pin<M>, mode: embedded_platform::gpio::Mode, initial_high: bool) -> Self {
+        use embedded_platform::gpio::Mode;
+
+        let level = if initial_high {
+            gpio::Level::High
+        } else {
+            gpio::Level::Low
+        };
+
+        match mode {
+            Mode::Disabled => State::Disabled(pin.into_disconnected()),
+            Mode::FloatingInput => State::FloatingInput(pin.into_floating_input()),
+            Mode::PullUpInput => State::PullUpInput(pin.into_pullup_input()),
+            Mode::PullDownInput => State::PullDownInput(pin.into_pulldown_input()),
+            Mode::OpenDrainOutput => State::OpenDrainOutput(
+                pin.into_open_drain_output(gpio::OpenDrainConfig::Disconnect0Standard1, level),
+            ),
+            Mode::PushPullOutput => State::PushPullOutput(pin.into_push_pull_output(level)),
+        }
+    }
+}
+
+macro_rules! any_pin_from {
+    ($($mode:ty => $variant:ident,)*) => {
+        $(
+            impl From<gpio::Pin<$mode>> for AnyPin {
+                fn from(pin: gpio::Pin<$mode>) -> Self {
+                    let state = Some(State::$variant(pin));
+                    AnyPin { state }
+                }
+            }
+        )*
+    };
+}
+
+any_pin_from! {
+    gpio::Disconnected => Disabled,
+    gpio::Input<gpio::Floating> => FloatingInput,
+    gpio::Input<gpio::PullUp> => PullUpInput,
+    gpio::Input<gpio::PullDown> => PullDownInput,
+    gpio::Output<gpio::OpenDrain> => OpenDrainOutput,
+    gpio::Output<gpio::PushPull> => PushPullOutput,
+}
+
+impl embedded_platform::gpio::Pin for AnyPin {
+    type Error = error::Error;
+}
+
+impl embedded_platform::gpio::InputPin for AnyPin {
+    fn poll_get(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        use embedded_hal::digital::v2::InputPin;
+        use embedded_hal::digital::v2::StatefulOutputPin;
+        use embedded_platform::gpio::FlexPin;
+
+        task::Poll::Ready(match self.state() {
+            State::FloatingInput(pin) => Ok(pin.is_high().unwrap()),
+            State::PullUpInput(pin) => Ok(pin.is_high().unwrap()),
+            State::PullDownInput(pin) => Ok(pin.is_high().unwrap()),
+            State::OpenDrainOutput(pin) => Ok(pin.is_high().unwrap()),
+            // The input buffer is disconnected in push-pull mode, so read back the latch instead
+            State::PushPullOutput(pin) => Ok(pin.is_set_high().unwrap()),
+            State::Disabled(_) => Err(error::Error::InvalidMode(self.mode())),
+        })
+    }
+}
+
+impl embedded_platform::gpio::OutputPin for AnyPin {
+    fn poll_set(
+        mut self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        high: bool,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        use embedded_hal::digital::v2::OutputPin;
+        use embedded_platform::gpio::FlexPin;
+
+        let mode = self.mode();
+        match self.state.as_mut().unwrap() {
+            State::OpenDrainOutput(pin) if high => pin.set_high().unwrap(),
+            State::OpenDrainOutput(pin) => pin.set_low().unwrap(),
+            State::PushPullOutput(pin) if high => pin.set_high().unwrap(),
+            State::PushPullOutput(pin) => pin.set_low().unwrap(),
+            _ => return task::Poll::Ready(Err(error::Error::InvalidMode(mode))),
+        }
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl embedded_platform::gpio::StatefulOutputPin for AnyPin {
+    fn poll_is_set_high(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        use embedded_hal::digital::v2::StatefulOutputPin;
+        use embedded_platform::gpio::FlexPin;
+
+        task::Poll::Ready(match self.state() {
+            State::OpenDrainOutput(pin) => Ok(pin.is_set_high().unwrap()),
+            State::PushPullOutput(pin) => Ok(pin.is_set_high().unwrap()),
+            _ => Err(error::Error::InvalidMode(self.mode())),
+        })
+    }
+}
+
+impl embedded_platform::gpio::FlexPin for AnyPin {
+    fn mode(&self) -> embedded_platform::gpio::Mode {
+        use embedded_platform::gpio::Mode;
+
+        match self.state() {
+            State::Disabled(_) => Mode::Disabled,
+            State::FloatingInput(_) => Mode::FloatingInput,
+            State::PullUpInput(_) => Mode::PullUpInput,
+            State::PullDownInput(_) => Mode::PullDownInput,
+            State::OpenDrainOutput(_) => Mode::OpenDrainOutput,
+            State::PushPullOutput(_) => Mode::PushPullOutput,
+        }
+    }
+
+    fn set_mode(
+        &mut self,
+        mode: embedded_platform::gpio::Mode,
+        initial_high: bool,
+    ) -> Result<(), Self::Error> {
+        let state = match self.state.take().unwrap() {
+            State::Disabled(pin) => State::new(pin, mode, initial_high),
+            State::FloatingInput(pin) => State::new(pin, mode, initial_high),
+            State::PullUpInput(pin) => State::new(pin, mode, initial_high),
+            State::PullDownInput(pin) => State::new(pin, mode, initial_high),
+            State::OpenDrainOutput(pin) => State::new(pin, mode, initial_high),
+            State::PushPullOutput(pin) => State::new(pin, mode, initial_high),
+        };
+        self.state = Some(state);
+        Ok(())
+    }
+}
+
+impl embedded_platform::gpio::IntoFlexPin for AnyPin {
+    type FlexPin = Self;
+
+    fn into_flex_pin(self) -> Result<Self::FlexPin, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl fmt::Debug for AnyPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use embedded_platform::gpio::FlexPin;
+
+        f.debug_struct("AnyPin")
+            .field("port", &self.port())
+            .field("index", &self.index())
+            .field("mode", &self.mode())
+            .finish()
+    }
+}
+
 macro_rules! gpio {
-    ($($m:ident: $mtyp:ident => [$($name:ident: $typ:ident,)*],)*) => {
+    ($($m:ident: $mtyp:ident = $port:expr => [$($name:ident: $typ:ident = $pin:expr,)*],)*) => {
+    /// The registry resources of all pins, by port and pin name.
+    pub(crate) mod resources {
+        $(
+            pub(crate) mod $m {
+                use embedded_platform::registry;
+
+                $(
+                    #[allow(non_upper_case_globals)]
+                    pub(crate) const $name: registry::Resource = registry::Resource::pin($port, $pin);
+                )*
+            }
+        )*
+    }
+
     $(
         #[derive(Debug)]
         pub(crate) struct $mtyp {
@@ -57,6 +295,24 @@ macro_rules! gpio {
             }
         }
 
+        impl<S> embedded_platform::gpio::StatefulOutputPin for Pin<$m::$typ<gpio::Output<S>>> where S: Unpin {
+            fn poll_is_set_high(
+                self: pin::Pin<&mut Self>,
+                _cx: &mut task::Context<'_>,
+            ) -> task::Poll<Result<bool, Self::Error>> {
+                use embedded_hal::digital::v2::StatefulOutputPin;
+                task::Poll::Ready(Ok(self.0.is_set_high().unwrap()))
+            }
+        }
+
+        impl<S> embedded_platform::gpio::IntoFlexPin for Pin<$m::$typ<S>> where S: Unpin, AnyPin: From<gpio::Pin<S>> {
+            type FlexPin = AnyPin;
+
+            fn into_flex_pin(self) -> Result<Self::FlexPin, Self::Error> {
+                Ok(AnyPin::from(self.0.degrade()))
+            }
+        }
+
         impl<S> embedded_platform::gpio::IntoFloatingInputPin for Pin<$m::$typ<S>> where S: Unpin {
             type FloatingInputPin = Pin<$m::$typ<gpio::Input<gpio::Floating>>>;
 
@@ -115,56 +371,56 @@ macro_rules! gpio {
 }
 
 gpio! {
-    p0: P0 => [
-        p0_00: P0_00,
-        p0_01: P0_01,
-        p0_02: P0_02,
-        p0_03: P0_03,
-        p0_04: P0_04,
-        p0_05: P0_05,
-        p0_06: P0_06,
-        p0_07: P0_07,
-        p0_08: P0_08,
-        p0_09: P0_09,
-        p0_10: P0_10,
-        p0_11: P0_11,
-        p0_12: P0_12,
-        p0_13: P0_13,
-        p0_14: P0_14,
-        p0_15: P0_15,
-        p0_16: P0_16,
-        p0_17: P0_17,
-        p0_18: P0_18,
-        p0_19: P0_19,
-        p0_20: P0_20,
-        p0_21: P0_21,
-        p0_22: P0_22,
-        p0_23: P0_23,
-        p0_24: P0_24,
-        p0_25: P0_25,
-        p0_26: P0_26,
-        p0_27: P0_27,
-        p0_28: P0_28,
-        p0_29: P0_29,
-        p0_30: P0_30,
-        p0_31: P0_31,
+    p0: P0 = 0 => [
+        p0_00: P0_00 = 0,
+        p0_01: P0_01 = 1,
+        p0_02: P0_02 = 2,
+        p0_03: P0_03 = 3,
+        p0_04: P0_04 = 4,
+        p0_05: P0_05 = 5,
+        p0_06: P0_06 = 6,
+        p0_07: P0_07 = 7,
+        p0_08: P0_08 = 8,
+        p0_09: P0_09 = 9,
+        p0_10: P0_10 = 10,
+        p0_11: P0_11 = 11,
+        p0_12: P0_12 = 12,
+        p0_13: P0_13 = 13,
+        p0_14: P0_14 = 14,
+        p0_15: P0_15 = 15,
+        p0_16: P0_16 = 16,
+        p0_17: P0_17 = 17,
+        p0_18: P0_18 = 18,
+        p0_19: P0_19 = 19,
+        p0_20: P0_20 = 20,
+        p0_21: P0_21 = 21,
+        p0_22: P0_22 = 22,
+        p0_23: P0_23 = 23,
+        p0_24: P0_24 = 24,
+        p0_25: P0_25 = 25,
+        p0_26: P0_26 = 26,
+        p0_27: P0_27 = 27,
+        p0_28: P0_28 = 28,
+        p0_29: P0_29 = 29,
+        p0_30: P0_30 = 30,
+        p0_31: P0_31 = 31,
     ],
-    p1: P1 => [
-        p1_00: P1_00,
-        p1_01: P1_01,
-        p1_02: P1_02,
-        p1_03: P1_03,
-        p1_04: P1_04,
-        p1_05: P1_05,
-        p1_06: P1_06,
-        p1_07: P1_07,
-        p1_08: P1_08,
-        p1_09: P1_09,
-        p1_10: P1_10,
-        p1_11: P1_11,
-        p1_12: P1_12,
-        p1_13: P1_13,
-        p1_14: P1_14,
-        p1_15: P1_15,
+    p1: P1 = 1 => [
+        p1_00: P1_00 = 0,
+        p1_01: P1_01 = 1,
+        p1_02: P1_02 = 2,
+        p1_03: P1_03 = 3,
+        p1_04: P1_04 = 4,
+        p1_05: P1_05 = 5,
+        p1_06: P1_06 = 6,
+        p1_07: P1_07 = 7,
+        p1_08: P1_08 = 8,
+        p1_09: P1_09 = 9,
+        p1_10: P1_10 = 10,
+        p1_11: P1_11 = 11,
+        p1_12: P1_12 = 12,
+        p1_13: P1_13 = 13,
+        p1_14: P1_14 = 14,
+        p1_15: P1_15 = 15,
     ],
 }
@@ -7,14 +7,39 @@ use core::task;
 use nrf52840_hal::gpio as hal_gpio;
 use nrf52840_hal::gpio::p0;
 
-#[derive(Clone, Copy, Debug)]
-pub struct I2c;
+/// The pin that the main I²C bus uses for `SDA`.
+type Sda = gpio::Pin<p0::P0_26<hal_gpio::Input<hal_gpio::Floating>>>;
+/// The pin that the main I²C bus uses for `SCL`.
+type Scl = gpio::Pin<p0::P0_27<hal_gpio::Input<hal_gpio::Floating>>>;
+
+/// The main I²C bus, which owns its `SDA` and `SCL` pins.
+///
+/// The TWIM peripheral isn't driven yet, so every transfer fails with
+/// [`error::Error::Unsupported`].
+#[derive(Debug)]
+pub struct I2c {
+    sda: Sda,
+    scl: Scl,
+}
 
+/// A read operation on the [`I2c`] bus, which can't be started yet.
 #[derive(Clone, Copy, Debug)]
-pub struct I2cRead {}
+pub enum I2cRead {}
 
+/// A write operation on the [`I2c`] bus, which can't be started yet.
 #[derive(Clone, Copy, Debug)]
-pub struct I2cWrite {}
+pub enum I2cWrite {}
+
+impl I2c {
+    pub(crate) fn new(sda: Sda, scl: Scl) -> Self {
+        I2c { sda, scl }
+    }
+
+    /// Consumes this bus, returning its `SDA` and `SCL` pins.
+    pub fn into_pins(self) -> (Sda, Scl) {
+        (self.sda, self.scl)
+    }
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct I2cMapping<SDA, SCL>(SDA, SCL);
@@ -41,7 +66,7 @@ impl
     where
         Self: Sized,
     {
-        unimplemented!()
+        task::Poll::Ready(Err(error::Error::Unsupported))
     }
 }
 
@@ -54,7 +79,7 @@ impl embedded_platform::i2c::I2cRead for I2c {
         cx: &mut task::Context<'_>,
         addr: u8,
     ) -> task::Poll<Result<Self::Read, Self::Error>> {
-        unimplemented!()
+        task::Poll::Ready(Err(error::Error::Unsupported))
     }
 }
 
@@ -66,7 +91,7 @@ impl embedded_platform::io::Read for I2cRead {
         cx: &mut task::Context<'_>,
         buffer: &mut [u8],
     ) -> task::Poll<Result<usize, Self::Error>> {
-        unimplemented!()
+        match *self {}
     }
 }
 
@@ -79,7 +104,7 @@ impl embedded_platform::i2c::I2cWrite for I2c {
         cx: &mut task::Context<'_>,
         addr: u8,
     ) -> task::Poll<Result<Self::Write, Self::Error>> {
-        unimplemented!()
+        task::Poll::Ready(Err(error::Error::Unsupported))
     }
 }
 
@@ -91,20 +116,20 @@ impl embedded_platform::io::Write for I2cWrite {
         cx: &mut task::Context<'_>,
         bytes: &[u8],
     ) -> task::Poll<Result<usize, Self::Error>> {
-        unimplemented!()
+        match *self {}
     }
 
     fn poll_flush(
         self: pin::Pin<&mut Self>,
         cx: &mut task::Context<'_>,
     ) -> task::Poll<Result<(), Self::Error>> {
-        unimplemented!()
+        match *self {}
     }
 
     fn poll_close(
         self: pin::Pin<&mut Self>,
         cx: &mut task::Context<'_>,
     ) -> task::Poll<Result<(), Self::Error>> {
-        unimplemented!()
+        match *self {}
     }
 }
@@ -15,8 +15,10 @@
 
 use core::future;
 use core::task;
+use embedded_platform::executor;
 use embedded_platform::platform;
-use embedded_platform::specs;
+use embedded_platform::platform::recovery;
+use embedded_platform::registry;
 
 pub mod error;
 pub mod gpio;
@@ -29,40 +31,85 @@ use nrf52840_hal::gpio as hal_gpio;
 use nrf52840_hal::gpio::p0;
 use nrf52840_hal::gpio::p1;
 
+/// The maximum number of tasks that can be spawned at the same time.
+pub const MAX_TASKS: usize = 8;
+/// The maximum size of the future of a spawned task, in bytes.
+pub const MAX_TASK_SIZE: usize = 1024;
+
+/// The number of resources that can be taken from a [`ParticleArgon`]: the pins of the Feather
+/// spec, and the timers.
+pub const RESOURCES: usize = 21 + 5;
+
+static EXECUTOR: executor::Executor<MAX_TASKS, MAX_TASK_SIZE> = executor::Executor::new();
+
 #[derive(Debug)]
 pub struct ParticleArgon {
     p0: gpio::P0,
     p1: gpio::P1,
     timers: timer::Timers,
+    registry: registry::Registry<RESOURCES>,
+    spawner: Option<executor::Spawner>,
 }
 
 impl platform::Platform for ParticleArgon {
     type Error = error::Error;
 
-    fn main<I, F>(run: I) -> !
+    /// Runs the application, letting `handler` decide what happens if it fails.
+    ///
+    /// The peripherals of a failed application can't be reclaimed, so the platform can only be
+    /// initialized once.  A [`recovery::Recovery::Restart`] is therefore carried out as a
+    /// [`recovery::Recovery::Reset`], which starts the application from scratch too, but also
+    /// starts counting [`recovery::Failure::attempt`]s from 1 again.
+    fn main_with<I, F, H>(run: I, mut handler: H) -> !
     where
-        I: FnOnce(Self) -> F,
+        I: FnMut(Self) -> F,
         F: future::Future<Output = Result<(), Self::Error>>,
+        H: recovery::Handler<Self::Error>,
     {
-        let future = async {
-            use embedded_platform::platform::PlatformExt;
-            let platform = ParticleArgon::initialize().await.unwrap();
-            run(platform).await.unwrap();
-
-            loop {
-                cortex_m::asm::wfi();
+        let handler = move |failure: recovery::Failure<Self::Error>| {
+            let handle = handler.handle(failure);
+            async move {
+                match handle.await {
+                    recovery::Recovery::Restart => recovery::Recovery::Reset,
+                    recovery => recovery,
+                }
+            }
+        };
+        let init = |spawner: executor::Spawner| async move {
+            let prepare = move |platform: &mut Self| platform.spawner = Some(spawner);
+            match recovery::supervise(run, handler, prepare).await {
+                // Keep running any spawned tasks.
+                Ok(()) => {}
+                Err(recovery::Recovery::Reset) => cortex_m::peripheral::SCB::sys_reset(),
+                Err(_) => {
+                    cortex_m::interrupt::disable();
+                    loop {
+                        cortex_m::asm::wfi();
+                    }
+                }
             }
         };
-        let wait = cortex_m::asm::wfi;
-        direct_executor::run(future, wait)
+        // Interrupts are masked while checking for woken tasks, so that one that wakes a task right
+        // after the check isn't handled before `wfi` (and then slept through).  `wfi` still wakes
+        // up when an interrupt becomes pending, which is then handled once they are unmasked.
+        let wait = || {
+            cortex_m::interrupt::free(|_| {
+                if !EXECUTOR.is_pending() {
+                    cortex_m::asm::wfi();
+                }
+            })
+        };
+        EXECUTOR.run(init, wait)
     }
 
     fn poll_initialize(_cx: &mut task::Context<'_>) -> task::Poll<Result<Self, Self::Error>> {
-        let mut core =
-            cortex_m::Peripherals::take().expect("A cortex_m platform was already initialized");
-
-        let peripherals = nrf52840_hal::nrf52840_pac::Peripherals::take()
-            .expect("A nrf52840 platform was already initialized");
+        let (mut core, peripherals) = match (
+            cortex_m::Peripherals::take(),
+            nrf52840_hal::nrf52840_pac::Peripherals::take(),
+        ) {
+            (Some(core), Some(peripherals)) => (core, peripherals),
+            _ => return task::Poll::Ready(Err(error::Error::AlreadyInitialized)),
+        };
 
         let p0 = gpio::P0::new(peripherals.P0);
         let p1 = gpio::P1::new(peripherals.P1);
@@ -76,7 +123,21 @@ impl platform::Platform for ParticleArgon {
             &mut core.NVIC,
         );
 
-        task::Poll::Ready(Ok(Self { p0, p1, timers }))
+        let registry = registry::Registry::new();
+        let spawner = None;
+
+        task::Poll::Ready(Ok(Self {
+            p0,
+            p1,
+            timers,
+            registry,
+            spawner,
+        }))
+    }
+
+    fn spawner(&self) -> executor::Spawner {
+        self.spawner
+            .expect("the platform was initialized outside of ParticleArgon::main")
     }
 }
 
@@ -84,152 +145,248 @@ impl ParticleArgon {
     pub fn take_timer0(
         &mut self,
     ) -> timer::Timer<nrf52840_hal::target::TIMER0, nrf52840_hal::timer::OneShot> {
-        self.timers.timer0.take().expect("timer 0 is already taken")
+        self.try_take_timer0().unwrap_or_else(|e| panic!("{}", e))
     }
 
     pub fn take_timer1(
         &mut self,
     ) -> timer::Timer<nrf52840_hal::target::TIMER1, nrf52840_hal::timer::OneShot> {
-        self.timers.timer1.take().expect("timer 1 is already taken")
+        self.try_take_timer1().unwrap_or_else(|e| panic!("{}", e))
     }
 
     pub fn take_timer2(
         &mut self,
     ) -> timer::Timer<nrf52840_hal::target::TIMER2, nrf52840_hal::timer::OneShot> {
-        self.timers.timer2.take().expect("timer 2 is already taken")
+        self.try_take_timer2().unwrap_or_else(|e| panic!("{}", e))
     }
 
     pub fn take_timer3(
         &mut self,
     ) -> timer::Timer<nrf52840_hal::target::TIMER3, nrf52840_hal::timer::OneShot> {
-        self.timers.timer3.take().expect("timer 3 is already taken")
+        self.try_take_timer3().unwrap_or_else(|e| panic!("{}", e))
     }
 
     pub fn take_timer4(
         &mut self,
     ) -> timer::Timer<nrf52840_hal::target::TIMER4, nrf52840_hal::timer::OneShot> {
-        self.timers.timer4.take().expect("timer 4 is already taken")
+        self.try_take_timer4().unwrap_or_else(|e| panic!("{}", e))
     }
-}
 
-impl specs::feather::Feather for ParticleArgon {
-    type MainLed = Self::D7;
-    type MainI2cMapping = i2c::I2cMapping<Self::SDA, Self::SCL>;
-
-    type SDA = gpio::Pin<p0::P0_26<hal_gpio::Input<hal_gpio::Floating>>>;
-    type SCL = gpio::Pin<p0::P0_27<hal_gpio::Input<hal_gpio::Floating>>>;
-    type D2 = gpio::Pin<p1::P1_01<hal_gpio::Input<hal_gpio::Floating>>>;
-    type D3 = gpio::Pin<p1::P1_02<hal_gpio::Input<hal_gpio::Floating>>>;
-    type D4 = gpio::Pin<p1::P1_08<hal_gpio::Input<hal_gpio::Floating>>>;
-    type D5 = gpio::Pin<p1::P1_10<hal_gpio::Input<hal_gpio::Floating>>>;
-    type D6 = gpio::Pin<p1::P1_11<hal_gpio::Input<hal_gpio::Floating>>>;
-    type D7 = gpio::Pin<p1::P1_12<hal_gpio::Input<hal_gpio::Floating>>>;
-    type D8 = gpio::Pin<p1::P1_03<hal_gpio::Input<hal_gpio::Floating>>>;
-    type P0 = gpio::Pin<p0::P0_11<hal_gpio::Input<hal_gpio::Floating>>>;
-    type TX = gpio::Pin<p0::P0_06<hal_gpio::Input<hal_gpio::Floating>>>;
-    type RX = gpio::Pin<p0::P0_08<hal_gpio::Input<hal_gpio::Floating>>>;
-    type MISO = gpio::Pin<p1::P1_14<hal_gpio::Input<hal_gpio::Floating>>>;
-    type MOSI = gpio::Pin<p1::P1_13<hal_gpio::Input<hal_gpio::Floating>>>;
-    type SCK = gpio::Pin<p1::P1_15<hal_gpio::Input<hal_gpio::Floating>>>;
-    type A5 = gpio::Pin<p0::P0_31<hal_gpio::Input<hal_gpio::Floating>>>;
-    type A4 = gpio::Pin<p0::P0_30<hal_gpio::Input<hal_gpio::Floating>>>;
-    type A3 = gpio::Pin<p0::P0_29<hal_gpio::Input<hal_gpio::Floating>>>;
-    type A2 = gpio::Pin<p0::P0_28<hal_gpio::Input<hal_gpio::Floating>>>;
-    type A1 = gpio::Pin<p0::P0_04<hal_gpio::Input<hal_gpio::Floating>>>;
-    type A0 = gpio::Pin<p0::P0_03<hal_gpio::Input<hal_gpio::Floating>>>;
-
-    fn take_main_led(&mut self) -> Self::MainLed {
-        self.p1.p1_12.take().expect("pin 1.12 is already taken")
-    }
-
-    fn take_main_i2c(
+    pub fn try_take_timer0(
         &mut self,
-    ) -> <Self::MainI2cMapping as embedded_platform::i2c::I2cBusMapping<Self::SDA, Self::SCL>>::Bus
-    {
-        unimplemented!()
-    }
-
-    fn take_sda(&mut self) -> Self::SDA {
-        self.p0.p0_26.take().expect("pin 0.26 is already taken")
-    }
-
-    fn take_scl(&mut self) -> Self::SCL {
-        self.p0.p0_27.take().expect("pin 0.27 is already taken")
-    }
-
-    fn take_d2(&mut self) -> Self::D2 {
-        self.p1.p1_01.take().expect("pin 1.01 is already taken")
-    }
-
-    fn take_d3(&mut self) -> Self::D3 {
-        self.p1.p1_02.take().expect("pin 1.02 is already taken")
+    ) -> Result<
+        timer::Timer<nrf52840_hal::target::TIMER0, nrf52840_hal::timer::OneShot>,
+        registry::ClaimError,
+    > {
+        let resource = registry::Resource::Timer(0);
+        take(
+            &mut self.registry,
+            &mut self.timers.timer0,
+            resource,
+            "timer0",
+        )
     }
 
-    fn take_d4(&mut self) -> Self::D4 {
-        self.p1.p1_08.take().expect("pin 1.08 is already taken")
-    }
-
-    fn take_d5(&mut self) -> Self::D5 {
-        self.p1.p1_10.take().expect("pin 1.10 is already taken")
+    pub fn try_take_timer1(
+        &mut self,
+    ) -> Result<
+        timer::Timer<nrf52840_hal::target::TIMER1, nrf52840_hal::timer::OneShot>,
+        registry::ClaimError,
+    > {
+        let resource = registry::Resource::Timer(1);
+        take(
+            &mut self.registry,
+            &mut self.timers.timer1,
+            resource,
+            "timer1",
+        )
     }
 
-    fn take_d6(&mut self) -> Self::D6 {
-        self.p1.p1_11.take().expect("pin 1.11 is already taken")
+    pub fn try_take_timer2(
+        &mut self,
+    ) -> Result<
+        timer::Timer<nrf52840_hal::target::TIMER2, nrf52840_hal::timer::OneShot>,
+        registry::ClaimError,
+    > {
+        let resource = registry::Resource::Timer(2);
+        take(
+            &mut self.registry,
+            &mut self.timers.timer2,
+            resource,
+            "timer2",
+        )
     }
 
-    fn take_d7(&mut self) -> Self::D7 {
-        self.p1.p1_12.take().expect("pin 1.12 is already taken")
+    pub fn try_take_timer3(
+        &mut self,
+    ) -> Result<
+        timer::Timer<nrf52840_hal::target::TIMER3, nrf52840_hal::timer::OneShot>,
+        registry::ClaimError,
+    > {
+        let resource = registry::Resource::Timer(3);
+        take(
+            &mut self.registry,
+            &mut self.timers.timer3,
+            resource,
+            "timer3",
+        )
     }
 
-    fn take_d8(&mut self) -> Self::D8 {
-        self.p1.p1_03.take().expect("pin 1.03 is already taken")
+    pub fn try_take_timer4(
+        &mut self,
+    ) -> Result<
+        timer::Timer<nrf52840_hal::target::TIMER4, nrf52840_hal::timer::OneShot>,
+        registry::ClaimError,
+    > {
+        let resource = registry::Resource::Timer(4);
+        take(
+            &mut self.registry,
+            &mut self.timers.timer4,
+            resource,
+            "timer4",
+        )
     }
 
-    fn take_p0(&mut self) -> Self::P0 {
-        self.p0.p0_11.take().expect("pin 0.11 is already taken")
+    pub fn release_timer0(
+        &mut self,
+        timer: timer::Timer<nrf52840_hal::target::TIMER0, nrf52840_hal::timer::OneShot>,
+    ) {
+        let resource = registry::Resource::Timer(0);
+        release(&mut self.registry, &mut self.timers.timer0, resource, timer)
     }
 
-    fn take_tx(&mut self) -> Self::TX {
-        self.p0.p0_06.take().expect("pin 0.06 is already taken")
+    pub fn release_timer1(
+        &mut self,
+        timer: timer::Timer<nrf52840_hal::target::TIMER1, nrf52840_hal::timer::OneShot>,
+    ) {
+        let resource = registry::Resource::Timer(1);
+        release(&mut self.registry, &mut self.timers.timer1, resource, timer)
     }
 
-    fn take_rx(&mut self) -> Self::RX {
-        self.p0.p0_08.take().expect("pin 0.08 is already taken")
+    pub fn release_timer2(
+        &mut self,
+        timer: timer::Timer<nrf52840_hal::target::TIMER2, nrf52840_hal::timer::OneShot>,
+    ) {
+        let resource = registry::Resource::Timer(2);
+        release(&mut self.registry, &mut self.timers.timer2, resource, timer)
     }
 
-    fn take_miso(&mut self) -> Self::MISO {
-        self.p1.p1_14.take().expect("pin 1.14 is already taken")
+    pub fn release_timer3(
+        &mut self,
+        timer: timer::Timer<nrf52840_hal::target::TIMER3, nrf52840_hal::timer::OneShot>,
+    ) {
+        let resource = registry::Resource::Timer(3);
+        release(&mut self.registry, &mut self.timers.timer3, resource, timer)
     }
 
-    fn take_mosi(&mut self) -> Self::MOSI {
-        self.p1.p1_13.take().expect("pin 1.13 is already taken")
+    pub fn release_timer4(
+        &mut self,
+        timer: timer::Timer<nrf52840_hal::target::TIMER4, nrf52840_hal::timer::OneShot>,
+    ) {
+        let resource = registry::Resource::Timer(4);
+        release(&mut self.registry, &mut self.timers.timer4, resource, timer)
     }
 
-    fn take_sck(&mut self) -> Self::SCK {
-        self.p1.p1_15.take().expect("pin 1.15 is already taken")
+    fn try_take_main_i2c_bus(&mut self) -> Result<i2c::I2c, registry::ClaimError> {
+        let sda = take(
+            &mut self.registry,
+            &mut self.p0.p0_26,
+            gpio::resources::p0::p0_26,
+            "main_i2c",
+        )?;
+        let scl = match take(
+            &mut self.registry,
+            &mut self.p0.p0_27,
+            gpio::resources::p0::p0_27,
+            "main_i2c",
+        ) {
+            Ok(scl) => scl,
+            Err(e) => {
+                let resource = gpio::resources::p0::p0_26;
+                release(&mut self.registry, &mut self.p0.p0_26, resource, sda);
+                return Err(e);
+            }
+        };
+        Ok(i2c::I2c::new(sda, scl))
     }
 
-    fn take_a5(&mut self) -> Self::A5 {
-        self.p0.p0_31.take().expect("pin 0.31 is already taken")
+    /// The registry of all resources that have been taken from this platform, and by whom.
+    pub fn registry(&self) -> &registry::Registry<RESOURCES> {
+        &self.registry
     }
+}
 
-    fn take_a4(&mut self) -> Self::A4 {
-        self.p0.p0_30.take().expect("pin 0.30 is already taken")
-    }
+macro_rules! take_pin {
+    ($platform:ident, $port:ident::$pin:ident, $claimant:expr) => {
+        take(
+            &mut $platform.registry,
+            &mut $platform.$port.$pin,
+            gpio::resources::$port::$pin,
+            $claimant,
+        )
+    };
+}
 
-    fn take_a3(&mut self) -> Self::A3 {
-        self.p0.p0_29.take().expect("pin 0.29 is already taken")
-    }
+macro_rules! release_pin {
+    ($platform:ident, $port:ident::$pin:ident, $value:expr) => {
+        release(
+            &mut $platform.registry,
+            &mut $platform.$port.$pin,
+            gpio::resources::$port::$pin,
+            $value,
+        )
+    };
+}
 
-    fn take_a2(&mut self) -> Self::A2 {
-        self.p0.p0_28.take().expect("pin 0.28 is already taken")
+embedded_platform::feather_board! {
+    impl Feather for ParticleArgon {
+        take = take_pin;
+        release = release_pin;
+        main_led => d7;
+        main_i2c: i2c::I2cMapping<Self::SDA, Self::SCL> => try_take_main_i2c_bus;
+
+        sda = p0::p0_26: gpio::Pin<p0::P0_26<hal_gpio::Input<hal_gpio::Floating>>>;
+        scl = p0::p0_27: gpio::Pin<p0::P0_27<hal_gpio::Input<hal_gpio::Floating>>>;
+        d2 = p1::p1_01: gpio::Pin<p1::P1_01<hal_gpio::Input<hal_gpio::Floating>>>;
+        d3 = p1::p1_02: gpio::Pin<p1::P1_02<hal_gpio::Input<hal_gpio::Floating>>>;
+        d4 = p1::p1_08: gpio::Pin<p1::P1_08<hal_gpio::Input<hal_gpio::Floating>>>;
+        d5 = p1::p1_10: gpio::Pin<p1::P1_10<hal_gpio::Input<hal_gpio::Floating>>>;
+        d6 = p1::p1_11: gpio::Pin<p1::P1_11<hal_gpio::Input<hal_gpio::Floating>>>;
+        d7 = p1::p1_12: gpio::Pin<p1::P1_12<hal_gpio::Input<hal_gpio::Floating>>>;
+        d8 = p1::p1_03: gpio::Pin<p1::P1_03<hal_gpio::Input<hal_gpio::Floating>>>;
+        p0 = p0::p0_11: gpio::Pin<p0::P0_11<hal_gpio::Input<hal_gpio::Floating>>>;
+        tx = p0::p0_06: gpio::Pin<p0::P0_06<hal_gpio::Input<hal_gpio::Floating>>>;
+        rx = p0::p0_08: gpio::Pin<p0::P0_08<hal_gpio::Input<hal_gpio::Floating>>>;
+        miso = p1::p1_14: gpio::Pin<p1::P1_14<hal_gpio::Input<hal_gpio::Floating>>>;
+        mosi = p1::p1_13: gpio::Pin<p1::P1_13<hal_gpio::Input<hal_gpio::Floating>>>;
+        sck = p1::p1_15: gpio::Pin<p1::P1_15<hal_gpio::Input<hal_gpio::Floating>>>;
+        a5 = p0::p0_31: gpio::Pin<p0::P0_31<hal_gpio::Input<hal_gpio::Floating>>>;
+        a4 = p0::p0_30: gpio::Pin<p0::P0_30<hal_gpio::Input<hal_gpio::Floating>>>;
+        a3 = p0::p0_29: gpio::Pin<p0::P0_29<hal_gpio::Input<hal_gpio::Floating>>>;
+        a2 = p0::p0_28: gpio::Pin<p0::P0_28<hal_gpio::Input<hal_gpio::Floating>>>;
+        a1 = p0::p0_04: gpio::Pin<p0::P0_04<hal_gpio::Input<hal_gpio::Floating>>>;
+        a0 = p0::p0_03: gpio::Pin<p0::P0_03<hal_gpio::Input<hal_gpio::Floating>>>;
     }
+}
 
-    fn take_a1(&mut self) -> Self::A1 {
-        self.p0.p0_04.take().expect("pin 0.04 is already taken")
-    }
+/// Claims a resource in the registry, and takes the corresponding peripheral out of its slot.
+fn take<T>(
+    registry: &mut registry::Registry<RESOURCES>,
+    slot: &mut Option<T>,
+    resource: registry::Resource,
+    claimant: &'static str,
+) -> Result<T, registry::ClaimError> {
+    registry.claim(resource, claimant)?;
+    Ok(slot.take().expect("the resource registry is out of sync"))
+}
 
-    fn take_a0(&mut self) -> Self::A0 {
-        self.p0.p0_03.take().expect("pin 0.03 is already taken")
-    }
+/// Puts a peripheral back in its slot, and releases the corresponding resource in the registry.
+fn release<T>(
+    registry: &mut registry::Registry<RESOURCES>,
+    slot: &mut Option<T>,
+    resource: registry::Resource,
+    value: T,
+) {
+    *slot = Some(value);
+    registry.release(resource);
 }
@@ -0,0 +1,131 @@
+//! Splitting byte streams into frames.
+//!
+//! Serial links like UARTs only transport raw bytes, so protocols on top of them need a way to
+//! tell where one message ends and the next one begins.  This module contains [`Encoder`]s and
+//! [`Decoder`]s for common framing schemes, none of which require allocation:
+//!
+//!   * [`Cobs`]: Consistent Overhead Byte Stuffing, with frames delimited by zero bytes.
+//!   * [`Slip`]: the Serial Line Internet Protocol framing from RFC 1055.
+//!   * [`LengthPrefixed`]: frames prefixed by their length as a big endian `u16`.
+//!
+//! [`FramedRead`] turns an [`io::Read`](crate::io::Read) into a `futures::Stream` of frames, and
+//! [`FramedWrite`] turns an [`io::Write`](crate::io::Write) into a `futures::Sink` of frames.
+use core::fmt;
+
+pub mod cobs;
+pub mod framed_read;
+pub mod framed_write;
+pub mod length_prefixed;
+pub mod slip;
+
+pub use cobs::Cobs;
+pub use framed_read::FramedRead;
+pub use framed_write::FramedWrite;
+pub use length_prefixed::LengthPrefixed;
+pub use slip::Slip;
+
+/// Decodes frames from a stream of bytes, one byte at a time.
+pub trait Decoder {
+    /// The error returned when the input is malformed.
+    type Error;
+
+    /// Feeds the next byte of input to the decoder, which stores any decoded data in `frame`.
+    ///
+    /// Returns the length of the frame once it is complete, after which the decoder starts
+    /// decoding a new frame at the start of `frame` again.  After returning an error, the decoder
+    /// skips ahead to the next frame.
+    fn decode(&mut self, byte: u8, frame: &mut [u8]) -> Result<Option<usize>, Self::Error>;
+
+    /// Whether the decoder is between frames, which means that the input can end here without
+    /// losing any data.
+    fn is_idle(&self) -> bool;
+}
+
+/// Encodes frames into bytes.
+pub trait Encoder {
+    /// The error returned when a frame can't be encoded.
+    type Error;
+
+    /// Encodes `frame` into `output`, returning the number of bytes used.
+    fn encode(&mut self, frame: &[u8], output: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// An error of the codecs in this module.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+    /// A frame was too long for the available buffer.
+    FrameTooLong,
+    /// The input was not encoded correctly.
+    Invalid,
+}
+
+/// An error of a [`FramedRead`] or [`FramedWrite`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FramedError<I, C> {
+    /// The underlying reader or writer failed.
+    Io(I),
+    /// A frame could not be encoded or decoded.
+    Codec(C),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::FrameTooLong => f.write_str("the frame is too long for the buffer"),
+            Error::Invalid => f.write_str("the frame is not encoded correctly"),
+        }
+    }
+}
+
+/// Appends `byte` to `frame`, failing if there is no more room.
+fn push(frame: &mut [u8], position: &mut usize, byte: u8) -> Result<(), Error> {
+    let slot = frame.get_mut(*position).ok_or(Error::FrameTooLong)?;
+    *slot = byte;
+    *position += 1;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decoder, Encoder, Error};
+
+    /// Checks that `frame` encodes to exactly `encoded`, and that it decodes back to `frame`.
+    pub(super) fn assert_round_trips<C>(frame: &[u8], encoded: &[u8])
+    where
+        C: Decoder<Error = Error> + Encoder<Error = Error> + Default,
+    {
+        let mut output = [0; 300];
+        let len = C::default().encode(frame, &mut output).unwrap();
+        assert_eq!(&output[..len], encoded);
+
+        assert_decodes(&mut C::default(), encoded, 300, &[Ok(frame)]);
+    }
+
+    /// Feeds `input` to `decoder`, using a frame buffer of `capacity` bytes, and checks that it
+    /// yields the `expected` frames and errors in order.
+    pub(super) fn assert_decodes<D>(
+        decoder: &mut D,
+        input: &[u8],
+        capacity: usize,
+        expected: &[Result<&[u8], Error>],
+    ) where
+        D: Decoder<Error = Error>,
+    {
+        let mut frame = [0; 300];
+        let frame = &mut frame[..capacity];
+        let mut outcomes = 0;
+
+        for &byte in input {
+            let outcome = match decoder.decode(byte, frame) {
+                Ok(None) => continue,
+                Ok(Some(len)) => Ok(&frame[..len]),
+                Err(error) => Err(error),
+            };
+            assert_eq!(Some(&outcome), expected.get(outcomes));
+            outcomes += 1;
+        }
+
+        assert_eq!(outcomes, expected.len());
+        assert!(decoder.is_idle());
+    }
+}
@@ -0,0 +1,189 @@
+/// Consistent Overhead Byte Stuffing, with frames delimited by zero bytes.
+///
+/// Encoding a frame of `n` bytes takes at most `n + n / 254 + 2` bytes.  Empty frames can be
+/// encoded, but lone delimiters between frames are skipped when decoding.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cobs {
+    position: usize,
+    code: u8,
+    remaining: u8,
+    discarding: bool,
+}
+
+impl Cobs {
+    /// Creates a new COBS codec.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl super::Decoder for Cobs {
+    type Error = super::Error;
+
+    fn decode(&mut self, byte: u8, frame: &mut [u8]) -> Result<Option<usize>, Self::Error> {
+        if byte == 0 {
+            let result = if self.discarding || self.code == 0 {
+                Ok(None)
+            } else if self.remaining > 0 {
+                Err(super::Error::Invalid)
+            } else {
+                Ok(Some(self.position))
+            };
+            *self = Self::new();
+            return result;
+        }
+
+        if self.discarding {
+            return Ok(None);
+        }
+
+        let result = if self.remaining == 0 {
+            // A block that is shorter than the maximum length stands for a zero byte, unless it
+            // was the last block of the frame, which is only known once the next block starts.
+            let result = if self.code != 0 && self.code != 0xff {
+                super::push(frame, &mut self.position, 0)
+            } else {
+                Ok(())
+            };
+            self.code = byte;
+            self.remaining = byte - 1;
+            result
+        } else {
+            self.remaining -= 1;
+            super::push(frame, &mut self.position, byte)
+        };
+
+        self.discarding = result.is_err();
+        result.map(|()| None)
+    }
+
+    fn is_idle(&self) -> bool {
+        self.code == 0 || self.discarding
+    }
+}
+
+impl super::Encoder for Cobs {
+    type Error = super::Error;
+
+    fn encode(&mut self, frame: &[u8], output: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut position = 1;
+        let mut code_position = 0;
+        let mut code = 1;
+
+        for (i, &byte) in frame.iter().enumerate() {
+            if byte != 0 {
+                super::push(output, &mut position, byte)?;
+                code += 1;
+            }
+            if byte == 0 || (code == 0xff && i + 1 < frame.len()) {
+                *output
+                    .get_mut(code_position)
+                    .ok_or(super::Error::FrameTooLong)? = code;
+                code_position = position;
+                super::push(output, &mut position, 0)?;
+                code = 1;
+            }
+        }
+
+        *output
+            .get_mut(code_position)
+            .ok_or(super::Error::FrameTooLong)? = code;
+        super::push(output, &mut position, 0)?;
+        Ok(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cobs;
+    use crate::codec::tests::{assert_decodes, assert_round_trips};
+    use crate::codec::Error;
+
+    #[test]
+    fn round_trips_short_frames() {
+        assert_round_trips::<Cobs>(&[], &[0x01, 0x00]);
+        assert_round_trips::<Cobs>(&[0x00], &[0x01, 0x01, 0x00]);
+        assert_round_trips::<Cobs>(&[0x00, 0x00], &[0x01, 0x01, 0x01, 0x00]);
+        assert_round_trips::<Cobs>(
+            &[0x11, 0x22, 0x00, 0x33],
+            &[0x03, 0x11, 0x22, 0x02, 0x33, 0x00],
+        );
+        assert_round_trips::<Cobs>(
+            &[0x11, 0x22, 0x33, 0x44],
+            &[0x05, 0x11, 0x22, 0x33, 0x44, 0x00],
+        );
+        assert_round_trips::<Cobs>(
+            &[0x11, 0x00, 0x00, 0x00],
+            &[0x02, 0x11, 0x01, 0x01, 0x01, 0x00],
+        );
+    }
+
+    #[test]
+    fn round_trips_frames_at_block_boundaries() {
+        let mut frame = [0; 256];
+        for (byte, value) in frame.iter_mut().zip(0..=0xff) {
+            *byte = value;
+        }
+        let mut encoded = [0; 258];
+
+        // 0x01..=0xfe fills exactly one block
+        encoded[0] = 0xff;
+        encoded[1..255].copy_from_slice(&frame[1..255]);
+        encoded[255] = 0x00;
+        assert_round_trips::<Cobs>(&frame[1..255], &encoded[..256]);
+
+        // 0x00..=0xfe starts with an empty block
+        encoded[0] = 0x01;
+        encoded[1] = 0xff;
+        encoded[2..256].copy_from_slice(&frame[1..255]);
+        encoded[256] = 0x00;
+        assert_round_trips::<Cobs>(&frame[..255], &encoded[..257]);
+
+        // 0x01..=0xff spills over into a second block
+        encoded[0] = 0xff;
+        encoded[1..255].copy_from_slice(&frame[1..255]);
+        encoded[255] = 0x02;
+        encoded[256] = 0xff;
+        encoded[257] = 0x00;
+        assert_round_trips::<Cobs>(&frame[1..256], &encoded[..258]);
+    }
+
+    #[test]
+    fn skips_lone_delimiters() {
+        assert_decodes(
+            &mut Cobs::new(),
+            &[0x00, 0x00, 0x02, 0x05, 0x00, 0x00],
+            4,
+            &[Ok(&[0x05])],
+        );
+    }
+
+    #[test]
+    fn recovers_from_a_frame_that_is_too_long() {
+        assert_decodes(
+            &mut Cobs::new(),
+            &[0x07, 1, 2, 3, 4, 5, 6, 0x00, 0x03, 0x11, 0x22, 0x00],
+            4,
+            &[Err(Error::FrameTooLong), Ok(&[0x11, 0x22])],
+        );
+    }
+
+    #[test]
+    fn recovers_from_a_truncated_block() {
+        assert_decodes(
+            &mut Cobs::new(),
+            &[0x05, 1, 2, 0x00, 0x03, 0x11, 0x22, 0x00],
+            4,
+            &[Err(Error::Invalid), Ok(&[0x11, 0x22])],
+        );
+    }
+
+    #[test]
+    fn rejects_too_small_outputs() {
+        use crate::codec::Encoder;
+
+        let mut output = [0; 4];
+        let result = Cobs::new().encode(&[1, 2, 3], &mut output);
+        assert_eq!(result, Err(Error::FrameTooLong));
+    }
+}
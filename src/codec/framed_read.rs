@@ -0,0 +1,209 @@
+use crate::io;
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// The number of bytes that are read from the underlying reader at a time.
+const CHUNK_SIZE: usize = 32;
+
+/// A `futures::Stream` of frames, decoded from an [`io::Read`](crate::io::Read).
+///
+/// Frames are decoded into a caller-provided buffer, which must be large enough for the longest
+/// expected frame.  Since the stream can't lend out the buffer, it yields the length of each frame,
+/// and the frame itself is available from [`frame`](FramedRead::frame) until the stream is polled
+/// again.
+///
+/// ```ignore
+/// let mut frames = FramedRead::new(uart, Cobs::new(), [0; 64]);
+/// while let Some(len) = frames.next().await {
+///     handle(&frames.frame()[..len?]);
+/// }
+/// ```
+///
+/// Decoding errors are yielded as items, after which decoding continues with the next frame.  The
+/// stream ends when the reader reaches its end between frames; if it ends in the middle of a frame,
+/// an [`eof`](crate::io::ReadError::eof) error is yielded first.
+pub struct FramedRead<R, D, B> {
+    reader: R,
+    decoder: D,
+    frame: B,
+    len: usize,
+    input: [u8; CHUNK_SIZE],
+    position: usize,
+    filled: usize,
+    done: bool,
+}
+
+impl<R, D, B> FramedRead<R, D, B> {
+    /// Creates a new stream that decodes frames from `reader` into `frame` using `decoder`.
+    pub fn new(reader: R, decoder: D, frame: B) -> Self {
+        let len = 0;
+        let input = [0; CHUNK_SIZE];
+        let position = 0;
+        let filled = 0;
+        let done = false;
+        FramedRead {
+            reader,
+            decoder,
+            frame,
+            len,
+            input,
+            position,
+            filled,
+            done,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader will skip past any data that has been read but
+    /// not decoded yet.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns a reference to the decoder.
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Consumes this stream, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R, D, B> FramedRead<R, D, B>
+where
+    B: AsRef<[u8]>,
+{
+    /// The most recently decoded frame.
+    pub fn frame(&self) -> &[u8] {
+        &self.frame.as_ref()[..self.len]
+    }
+}
+
+impl<R, D, B> futures::Stream for FramedRead<R, D, B>
+where
+    R: io::Read + Unpin,
+    D: super::Decoder + Unpin,
+    B: AsMut<[u8]> + Unpin,
+{
+    type Item = Result<usize, super::FramedError<R::Error, D::Error>>;
+
+    fn poll_next(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        use io::ReadError;
+
+        let this = &mut *self;
+        this.len = 0;
+
+        loop {
+            while this.position < this.filled {
+                let byte = this.input[this.position];
+                this.position += 1;
+                match this.decoder.decode(byte, this.frame.as_mut()) {
+                    Ok(Some(len)) => {
+                        this.len = len;
+                        return task::Poll::Ready(Some(Ok(len)));
+                    }
+                    Ok(None) => {}
+                    Err(error) => {
+                        return task::Poll::Ready(Some(Err(super::FramedError::Codec(error))))
+                    }
+                }
+            }
+
+            if this.done {
+                return task::Poll::Ready(None);
+            }
+
+            let n = futures::ready!(pin::Pin::new(&mut this.reader).poll_read(cx, &mut this.input))
+                .map_err(super::FramedError::Io)?;
+            this.position = 0;
+            this.filled = n;
+
+            if n == 0 {
+                this.done = true;
+                if !this.decoder.is_idle() {
+                    let error = super::FramedError::Io(R::Error::eof());
+                    return task::Poll::Ready(Some(Err(error)));
+                }
+            }
+        }
+    }
+}
+
+impl<R, D, B> fmt::Debug for FramedRead<R, D, B>
+where
+    R: fmt::Debug,
+    D: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FramedRead")
+            .field("reader", &self.reader)
+            .field("decoder", &self.decoder)
+            .field("len", &self.len)
+            .field("buffered", &(self.filled - self.position))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FramedRead;
+    use crate::codec::{Cobs, Error, FramedError, LengthPrefixed};
+    use crate::io;
+    use futures::StreamExt;
+
+    #[test]
+    fn reads_frames_across_chunks() {
+        futures::executor::block_on(async {
+            let mut input = [0x01; 2 * super::CHUNK_SIZE];
+            input[..2].copy_from_slice(&[0x00, 0x28]);
+            input[0x2a..0x2c].copy_from_slice(&[0x00, 0x14]);
+            let mut frames =
+                FramedRead::new(io::Cursor::new(input), LengthPrefixed::new(), [0; 64]);
+
+            assert_eq!(frames.next().await, Some(Ok(0x28)));
+            assert_eq!(frames.frame(), &[0x01; 0x28][..]);
+            assert_eq!(frames.next().await, Some(Ok(0x14)));
+            assert_eq!(frames.frame(), &[0x01; 0x14][..]);
+            assert_eq!(frames.next().await, None);
+        });
+    }
+
+    #[test]
+    fn recovers_from_a_frame_that_is_too_long() {
+        futures::executor::block_on(async {
+            let input = [0x07, 1, 2, 3, 4, 5, 6, 0x00, 0x03, 0x11, 0x22, 0x00];
+            let mut frames = FramedRead::new(io::Cursor::new(input), Cobs::new(), [0; 4]);
+
+            let error = FramedError::Codec(Error::FrameTooLong);
+            assert_eq!(frames.next().await, Some(Err(error)));
+            assert_eq!(frames.next().await, Some(Ok(2)));
+            assert_eq!(frames.frame(), &[0x11, 0x22]);
+            assert_eq!(frames.next().await, None);
+        });
+    }
+
+    #[test]
+    fn reports_the_end_of_input_in_the_middle_of_a_frame() {
+        futures::executor::block_on(async {
+            let input = [0x02, 0x11, 0x00, 0x03, 0x11];
+            let mut frames = FramedRead::new(io::Cursor::new(input), Cobs::new(), [0; 4]);
+
+            assert_eq!(frames.next().await, Some(Ok(1)));
+            let error = FramedError::Io(io::Error::UnexpectedEof);
+            assert_eq!(frames.next().await, Some(Err(error)));
+            assert_eq!(frames.next().await, None);
+        });
+    }
+}
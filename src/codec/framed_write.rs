@@ -0,0 +1,152 @@
+use crate::io;
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// A `futures::Sink` of frames, encoded into an [`io::Write`](crate::io::Write).
+///
+/// Each frame is encoded into a caller-provided buffer, which must be large enough for the
+/// encoding of the longest expected frame, and is then written to the underlying writer before
+/// the next frame is accepted.
+///
+/// ```ignore
+/// let mut frames = FramedWrite::new(uart, Cobs::new(), [0; 64]);
+/// frames.send(b"hello").await?;
+/// ```
+pub struct FramedWrite<W, E, B> {
+    writer: W,
+    encoder: E,
+    buffer: B,
+    position: usize,
+    filled: usize,
+}
+
+impl<W, E, B> FramedWrite<W, E, B> {
+    /// Creates a new sink that encodes frames into `buffer` using `encoder`, and writes them to
+    /// `writer`.
+    pub fn new(writer: W, encoder: E, buffer: B) -> Self {
+        let position = 0;
+        let filled = 0;
+        FramedWrite {
+            writer,
+            encoder,
+            buffer,
+            position,
+            filled,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer might interleave with an encoded frame that has
+    /// not been written completely yet.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns a reference to the encoder.
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Consumes this sink, returning the underlying writer.
+    ///
+    /// Any encoded frame that hasn't been written completely yet is lost.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, E, B> FramedWrite<W, E, B>
+where
+    W: io::Write + Unpin,
+    B: AsRef<[u8]>,
+{
+    /// Writes the encoded frame (if any) to the underlying writer, without flushing it.
+    fn poll_write_buffer(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), W::Error>> {
+        use io::WriteError;
+
+        while self.position < self.filled {
+            let n = futures::ready!(pin::Pin::new(&mut self.writer)
+                .poll_write(cx, &self.buffer.as_ref()[self.position..self.filled]))?;
+            if n == 0 {
+                return task::Poll::Ready(Err(W::Error::write_zero()));
+            }
+            self.position += n;
+        }
+
+        self.position = 0;
+        self.filled = 0;
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl<W, E, B> futures::Sink<&[u8]> for FramedWrite<W, E, B>
+where
+    W: io::Write + Unpin,
+    E: super::Encoder + Unpin,
+    B: AsRef<[u8]> + AsMut<[u8]> + Unpin,
+{
+    type Error = super::FramedError<W::Error, E::Error>;
+
+    fn poll_ready(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        self.poll_write_buffer(cx).map_err(super::FramedError::Io)
+    }
+
+    fn start_send(mut self: pin::Pin<&mut Self>, frame: &[u8]) -> Result<(), Self::Error> {
+        let this = &mut *self;
+        assert_eq!(this.filled, 0, "start_send called without poll_ready");
+        this.filled = this
+            .encoder
+            .encode(frame, this.buffer.as_mut())
+            .map_err(super::FramedError::Codec)?;
+        Ok(())
+    }
+
+    fn poll_flush(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = &mut *self;
+        futures::ready!(this.poll_write_buffer(cx)).map_err(super::FramedError::Io)?;
+        pin::Pin::new(&mut this.writer)
+            .poll_flush(cx)
+            .map_err(super::FramedError::Io)
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = &mut *self;
+        futures::ready!(this.poll_write_buffer(cx)).map_err(super::FramedError::Io)?;
+        pin::Pin::new(&mut this.writer)
+            .poll_close(cx)
+            .map_err(super::FramedError::Io)
+    }
+}
+
+impl<W, E, B> fmt::Debug for FramedWrite<W, E, B>
+where
+    W: fmt::Debug,
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FramedWrite")
+            .field("writer", &self.writer)
+            .field("encoder", &self.encoder)
+            .field("buffered", &(self.filled - self.position))
+            .finish()
+    }
+}
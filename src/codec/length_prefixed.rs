@@ -0,0 +1,120 @@
+use core::convert::TryFrom;
+
+/// Frames that are prefixed by their length, as a big endian `u16`.
+///
+/// Encoding a frame of `n` bytes takes `n + 2` bytes.  Since there is no delimiter to
+/// resynchronize on, this framing is best suited for reliable links.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LengthPrefixed {
+    header: [u8; 2],
+    header_len: usize,
+    length: usize,
+    position: usize,
+    discarding: bool,
+}
+
+impl LengthPrefixed {
+    /// Creates a new length-prefixed codec.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl super::Decoder for LengthPrefixed {
+    type Error = super::Error;
+
+    fn decode(&mut self, byte: u8, frame: &mut [u8]) -> Result<Option<usize>, Self::Error> {
+        if self.header_len < self.header.len() {
+            self.header[self.header_len] = byte;
+            self.header_len += 1;
+            if self.header_len < self.header.len() {
+                return Ok(None);
+            }
+
+            self.length = usize::from(u16::from_be_bytes(self.header));
+            if self.length == 0 {
+                *self = Self::new();
+                return Ok(Some(0));
+            }
+            if self.length > frame.len() {
+                // Skip the frame's data, to be able to decode the frame after it
+                self.discarding = true;
+                return Err(super::Error::FrameTooLong);
+            }
+            return Ok(None);
+        }
+
+        if !self.discarding {
+            frame[self.position] = byte;
+        }
+        self.position += 1;
+
+        if self.position < self.length {
+            Ok(None)
+        } else {
+            let result = if self.discarding {
+                None
+            } else {
+                Some(self.length)
+            };
+            *self = Self::new();
+            Ok(result)
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.header_len == 0
+    }
+}
+
+impl super::Encoder for LengthPrefixed {
+    type Error = super::Error;
+
+    fn encode(&mut self, frame: &[u8], output: &mut [u8]) -> Result<usize, Self::Error> {
+        let length = u16::try_from(frame.len()).map_err(|_| super::Error::FrameTooLong)?;
+        let total = frame.len() + 2;
+        if output.len() < total {
+            return Err(super::Error::FrameTooLong);
+        }
+
+        output[..2].copy_from_slice(&length.to_be_bytes());
+        output[2..total].copy_from_slice(frame);
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LengthPrefixed;
+    use crate::codec::tests::{assert_decodes, assert_round_trips};
+    use crate::codec::{Encoder, Error};
+
+    #[test]
+    fn round_trips_frames() {
+        assert_round_trips::<LengthPrefixed>(&[], &[0x00, 0x00]);
+        assert_round_trips::<LengthPrefixed>(&[0x00], &[0x00, 0x01, 0x00]);
+        assert_round_trips::<LengthPrefixed>(&[0x11, 0x22, 0x33], &[0x00, 0x03, 0x11, 0x22, 0x33]);
+
+        let frame = [0xaa; 258];
+        let mut encoded = [0xaa; 260];
+        encoded[..2].copy_from_slice(&[0x01, 0x02]);
+        assert_round_trips::<LengthPrefixed>(&frame, &encoded);
+    }
+
+    #[test]
+    fn recovers_from_a_frame_that_is_too_long() {
+        assert_decodes(
+            &mut LengthPrefixed::new(),
+            &[0x00, 0x06, 1, 2, 3, 4, 5, 6, 0x00, 0x02, 0x11, 0x22],
+            4,
+            &[Err(Error::FrameTooLong), Ok(&[0x11, 0x22])],
+        );
+    }
+
+    #[test]
+    fn rejects_too_small_outputs() {
+        let mut output = [0; 4];
+        let result = LengthPrefixed::new().encode(&[1, 2, 3], &mut output);
+        assert_eq!(result, Err(Error::FrameTooLong));
+    }
+}
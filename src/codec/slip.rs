@@ -0,0 +1,145 @@
+const END: u8 = 0xc0;
+const ESC: u8 = 0xdb;
+const ESC_END: u8 = 0xdc;
+const ESC_ESC: u8 = 0xdd;
+
+/// The Serial Line Internet Protocol framing from RFC 1055.
+///
+/// Frames are both preceded and followed by an `END` byte, which flushes out any line noise that
+/// was received before the frame.  Encoding a frame of `n` bytes takes at most `2 * n + 2` bytes.
+/// Empty frames can't be represented, and are skipped when decoding.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Slip {
+    position: usize,
+    escaped: bool,
+    discarding: bool,
+}
+
+impl Slip {
+    /// Creates a new SLIP codec.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl super::Decoder for Slip {
+    type Error = super::Error;
+
+    fn decode(&mut self, byte: u8, frame: &mut [u8]) -> Result<Option<usize>, Self::Error> {
+        if byte == END {
+            let result = if self.discarding || (self.position == 0 && !self.escaped) {
+                Ok(None)
+            } else if self.escaped {
+                Err(super::Error::Invalid)
+            } else {
+                Ok(Some(self.position))
+            };
+            *self = Self::new();
+            return result;
+        }
+
+        if self.discarding {
+            return Ok(None);
+        }
+
+        let result = if self.escaped {
+            self.escaped = false;
+            match byte {
+                ESC_END => super::push(frame, &mut self.position, END),
+                ESC_ESC => super::push(frame, &mut self.position, ESC),
+                _ => Err(super::Error::Invalid),
+            }
+        } else if byte == ESC {
+            self.escaped = true;
+            Ok(())
+        } else {
+            super::push(frame, &mut self.position, byte)
+        };
+
+        self.discarding = result.is_err();
+        result.map(|()| None)
+    }
+
+    fn is_idle(&self) -> bool {
+        (self.position == 0 && !self.escaped) || self.discarding
+    }
+}
+
+impl super::Encoder for Slip {
+    type Error = super::Error;
+
+    fn encode(&mut self, frame: &[u8], output: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut position = 0;
+        super::push(output, &mut position, END)?;
+        for &byte in frame {
+            match byte {
+                END => {
+                    super::push(output, &mut position, ESC)?;
+                    super::push(output, &mut position, ESC_END)?;
+                }
+                ESC => {
+                    super::push(output, &mut position, ESC)?;
+                    super::push(output, &mut position, ESC_ESC)?;
+                }
+                _ => super::push(output, &mut position, byte)?,
+            }
+        }
+        super::push(output, &mut position, END)?;
+        Ok(position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Slip, END, ESC, ESC_END, ESC_ESC};
+    use crate::codec::tests::{assert_decodes, assert_round_trips};
+    use crate::codec::Error;
+
+    #[test]
+    fn round_trips_frames() {
+        assert_round_trips::<Slip>(&[0x01], &[END, 0x01, END]);
+        assert_round_trips::<Slip>(&[0x01, 0x02, 0x03], &[END, 0x01, 0x02, 0x03, END]);
+        assert_round_trips::<Slip>(&[END], &[END, ESC, ESC_END, END]);
+        assert_round_trips::<Slip>(&[ESC], &[END, ESC, ESC_ESC, END]);
+        assert_round_trips::<Slip>(
+            &[0x01, END, 0x02, ESC, 0x03],
+            &[END, 0x01, ESC, ESC_END, 0x02, ESC, ESC_ESC, 0x03, END],
+        );
+    }
+
+    #[test]
+    fn skips_empty_frames() {
+        assert_decodes(
+            &mut Slip::new(),
+            &[END, END, END, 0x05, END, END],
+            4,
+            &[Ok(&[0x05])],
+        );
+    }
+
+    #[test]
+    fn recovers_from_a_frame_that_is_too_long() {
+        assert_decodes(
+            &mut Slip::new(),
+            &[END, 1, 2, 3, 4, 5, 6, END, END, 0x11, 0x22, END],
+            4,
+            &[Err(Error::FrameTooLong), Ok(&[0x11, 0x22])],
+        );
+    }
+
+    #[test]
+    fn recovers_from_invalid_escapes() {
+        assert_decodes(
+            &mut Slip::new(),
+            &[END, 0x01, ESC, 0x05, 0x06, END, 0x03, END],
+            4,
+            &[Err(Error::Invalid), Ok(&[0x03])],
+        );
+        assert_decodes(
+            &mut Slip::new(),
+            &[END, 0x01, ESC, END, 0x03, END],
+            4,
+            &[Err(Error::Invalid), Ok(&[0x03])],
+        );
+    }
+}
@@ -0,0 +1,9 @@
+//! Running several tasks concurrently.
+//!
+//! Every platform runs its `main` future on an [`Executor`], which doesn't require `alloc`: tasks
+//! live in a fixed number of statically allocated slots, and only tasks that have actually been
+//! woken are polled again.  Applications can start additional tasks using the [`Spawner`] returned
+//! by [`Platform::spawner`](crate::platform::Platform::spawner).
+pub use core::executor::Executor;
+pub use core::executor::SpawnError;
+pub use core::executor::Spawner;
@@ -3,14 +3,39 @@
 //! The [`InputPin`] and [`OutputPin`] traits define pins that can be read and written digitally
 //! (i.e. either in a low or high state).
 //!
+//! Input pins that implement [`EdgeInputPin`] can additionally be waited on until their level
+//! changes, for example with [`InputPinExt::wait_for_rising_edge`] or [`InputPinExt::changes`].
+//!
 //! There are additionally various `Into*` traits that allow users to re-configure pins to switch
 //! between different modes of operation, e.g. [`IntoFloatingInputPin`] turns a pin into an
 //! [`InputPin`] that does not employ any pull-up or pull-down resistors.
+//!
+//! Alternatively, [`IntoFlexPin`] turns a pin into a [`FlexPin`], whose mode can be changed at
+//! runtime without changing its type.
+//!
+//! Signals that are active when they are low, like many LEDs and chip selects, can be wrapped in an
+//! [`Inverted`] pin, so that "high" always means "active".
+use crate::io;
 use core::pin;
 use core::task;
 
+pub mod button;
+pub mod changes;
+pub mod debounced;
 pub mod get;
+pub mod inverted;
+pub mod is_set_high;
 pub mod set;
+pub mod stateful;
+pub mod toggle;
+pub mod wait_for_edge;
+pub mod wait_for_level;
+
+pub use button::Button;
+pub use button::ButtonEvent;
+pub use debounced::Debounced;
+pub use inverted::Inverted;
+pub use stateful::Stateful;
 
 /// A generic pin that can't be interacted with.
 pub trait Pin {
@@ -38,9 +63,98 @@ pub trait InputPinExt: InputPin {
     {
         get::get(self)
     }
+
+    /// Waits for the specified edge, resolving to the level of this pin after it.
+    fn wait_for_edge(&mut self, edge: Edge) -> wait_for_edge::WaitForEdge<Self>
+    where
+        Self: EdgeInputPin + Unpin,
+    {
+        wait_for_edge::wait_for_edge(self, edge)
+    }
+
+    /// Waits for this pin to go from a low to a high level.
+    fn wait_for_rising_edge(&mut self) -> wait_for_edge::WaitForEdge<Self>
+    where
+        Self: EdgeInputPin + Unpin,
+    {
+        wait_for_edge::wait_for_edge(self, Edge::Rising)
+    }
+
+    /// Waits for this pin to go from a high to a low level.
+    fn wait_for_falling_edge(&mut self) -> wait_for_edge::WaitForEdge<Self>
+    where
+        Self: EdgeInputPin + Unpin,
+    {
+        wait_for_edge::wait_for_edge(self, Edge::Falling)
+    }
+
+    /// Waits for this pin to be at a high level, resolving immediately if it already is.
+    fn wait_for_high(&mut self) -> wait_for_level::WaitForLevel<Self>
+    where
+        Self: EdgeInputPin + Unpin,
+    {
+        wait_for_level::wait_for_level(self, true)
+    }
+
+    /// Waits for this pin to be at a low level, resolving immediately if it already is.
+    fn wait_for_low(&mut self) -> wait_for_level::WaitForLevel<Self>
+    where
+        Self: EdgeInputPin + Unpin,
+    {
+        wait_for_level::wait_for_level(self, false)
+    }
+
+    /// Returns a stream of the levels of this pin, that yields every time the pin changes.
+    fn changes(&mut self) -> changes::Changes<Self>
+    where
+        Self: EdgeInputPin + Unpin,
+    {
+        changes::changes(self)
+    }
 }
 
-impl<A> InputPinExt for A where A: InputPin {}
+impl<A> InputPinExt for A where A: InputPin + ?Sized {}
+
+/// A change of the level of a pin.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Edge {
+    /// The pin went from a low to a high level.
+    Rising,
+    /// The pin went from a high to a low level.
+    Falling,
+    /// The pin went from any level to the other.
+    Any,
+}
+
+impl Edge {
+    /// Whether a change of a pin to the specified level is this kind of edge.
+    pub fn matches(self, high: bool) -> bool {
+        match self {
+            Edge::Rising => high,
+            Edge::Falling => !high,
+            Edge::Any => true,
+        }
+    }
+}
+
+/// An [`InputPin`] that can wait for its level to change.
+///
+/// Edges are relative to the level that the pin last reported, either through
+/// [`poll_get`](InputPin::poll_get) or through [`poll_wait_for_edge`](Self::poll_wait_for_edge).
+/// A pin that has changed since it was last read therefore reports that edge right away, which
+/// means that no changes are missed between two waits.
+pub trait EdgeInputPin: InputPin {
+    /// Polls a wait for the specified edge to completion, returning the level of the pin after
+    /// it.
+    ///
+    /// Edges of the other direction are skipped when waiting for [`Edge::Rising`] or
+    /// [`Edge::Falling`].
+    fn poll_wait_for_edge(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        edge: Edge,
+    ) -> task::Poll<Result<bool, Self::Error>>;
+}
 
 /// A pin that can be written to.
 pub trait OutputPin: Pin {
@@ -61,9 +175,148 @@ pub trait OutputPinExt: OutputPin {
     {
         set::set(self, high)
     }
+
+    /// Gets whether this pin is currently set to a high level.
+    fn is_set_high(&mut self) -> is_set_high::IsSetHigh<Self>
+    where
+        Self: StatefulOutputPin + Unpin,
+    {
+        is_set_high::is_set_high(self)
+    }
+
+    /// Switches this pin to the other level.
+    fn toggle(&mut self) -> toggle::Toggle<Self>
+    where
+        Self: ToggleableOutputPin + Unpin,
+    {
+        toggle::toggle(self)
+    }
+}
+
+impl<A> OutputPinExt for A where A: OutputPin + ?Sized {}
+
+/// An [`OutputPin`] that can read back the level that it is currently set to.
+///
+/// Pins that can't read back their output latch can be wrapped in a [`Stateful`], which remembers
+/// the last level that was written instead.
+pub trait StatefulOutputPin: OutputPin {
+    /// Polls a read of the output latch of this pin to completion, returning whether the pin is
+    /// set to a high level.
+    fn poll_is_set_high(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>>;
 }
 
-impl<A> OutputPinExt for A where A: OutputPin {}
+/// An [`OutputPin`] that can switch to the other level without the caller knowing the current
+/// one.
+///
+/// This is implemented for all [`StatefulOutputPin`]s, by reading back the level and then writing
+/// the other one.
+pub trait ToggleableOutputPin: OutputPin {
+    /// Polls a toggle operation of this pin to completion.
+    fn poll_toggle(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>>;
+}
+
+impl<A> ToggleableOutputPin for A
+where
+    A: StatefulOutputPin + ?Sized,
+{
+    fn poll_toggle(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        // The latch only changes once the write completes, so polling again after the write was
+        // pending reads the same level and retries the same write
+        let high = futures::ready!(self.as_mut().poll_is_set_high(cx))?;
+        self.poll_set(cx, !high)
+    }
+}
+
+/// An object-safe version of [`InputPin`], with [`io::ErrorKind`] as its fixed error type.
+///
+/// This is implemented for every [`InputPin`] that is `Unpin` and whose errors convert into
+/// [`io::ErrorKind`], and `dyn DynInputPin` implements [`InputPin`] in turn, so pins of different
+/// types can be kept in one table as `&mut dyn DynInputPin`.
+pub trait DynInputPin: Unpin {
+    /// Polls a read operation of this pin, like [`InputPin::poll_get`].
+    fn poll_get(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<bool, io::ErrorKind>>;
+}
+
+impl<A> DynInputPin for A
+where
+    A: InputPin + Unpin,
+    A::Error: Into<io::ErrorKind>,
+{
+    fn poll_get(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<bool, io::ErrorKind>> {
+        InputPin::poll_get(pin::Pin::new(self), cx).map_err(Into::into)
+    }
+}
+
+impl Pin for dyn DynInputPin + '_ {
+    type Error = io::ErrorKind;
+}
+
+impl InputPin for dyn DynInputPin + '_ {
+    fn poll_get(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        DynInputPin::poll_get(self.get_mut(), cx)
+    }
+}
+
+/// An object-safe version of [`OutputPin`], with [`io::ErrorKind`] as its fixed error type.
+///
+/// This is implemented for every [`OutputPin`] that is `Unpin` and whose errors convert into
+/// [`io::ErrorKind`], and `dyn DynOutputPin` implements [`OutputPin`] in turn.  This makes it
+/// possible to, for example, drive a list of status LEDs that are connected to different ports:
+///
+/// ```ignore
+/// let mut leds: [&mut dyn DynOutputPin; 3] = [&mut red, &mut green, &mut blue];
+/// for led in leds.iter_mut() {
+///     led.set(true).await?;
+/// }
+/// ```
+pub trait DynOutputPin: Unpin {
+    /// Polls a write operation of this pin, like [`OutputPin::poll_set`].
+    fn poll_set(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        high: bool,
+    ) -> task::Poll<Result<(), io::ErrorKind>>;
+}
+
+impl<A> DynOutputPin for A
+where
+    A: OutputPin + Unpin,
+    A::Error: Into<io::ErrorKind>,
+{
+    fn poll_set(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        high: bool,
+    ) -> task::Poll<Result<(), io::ErrorKind>> {
+        OutputPin::poll_set(pin::Pin::new(self), cx, high).map_err(Into::into)
+    }
+}
+
+impl Pin for dyn DynOutputPin + '_ {
+    type Error = io::ErrorKind;
+}
+
+impl OutputPin for dyn DynOutputPin + '_ {
+    fn poll_set(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        high: bool,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        DynOutputPin::poll_set(self.get_mut(), cx, high)
+    }
+}
 
 /// A pin that can be turned into an [`InputPin`] that does not employ any pull-up or pull-down
 /// resistors.
@@ -117,6 +370,47 @@ pub trait IntoPushPullOutputPin: Pin {
     ) -> Result<Self::PushPullOutputPin, Self::Error>;
 }
 
+/// A mode of operation that a [`FlexPin`] can be switched to at runtime.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Mode {
+    /// The pin is neither read nor driven, which uses the least power.
+    Disabled,
+    /// The pin is an input that does not employ any pull-up or pull-down resistors.
+    FloatingInput,
+    /// The pin is an input that has a pull-up resistor attached.
+    PullUpInput,
+    /// The pin is an input that has a pull-down resistor attached.
+    PullDownInput,
+    /// The pin is an output in open drain mode.
+    OpenDrainOutput,
+    /// The pin is an output in push-pull mode.
+    PushPullOutput,
+}
+
+/// A pin whose mode is chosen at runtime rather than through its type.
+///
+/// Such a pin is an [`InputPin`] and an [`OutputPin`] at the same time, which is useful for
+/// bidirectional data lines like a one-wire bus, or for pin tables that are configured at startup.
+/// Operations that the current mode doesn't support result in an error.
+pub trait FlexPin: InputPin + OutputPin {
+    /// The mode that this pin is currently in.
+    fn mode(&self) -> Mode;
+
+    /// Attempts to re-configure this pin into the specified mode.
+    ///
+    /// The level `initial_high` is only used when switching to one of the output modes.
+    fn set_mode(&mut self, mode: Mode, initial_high: bool) -> Result<(), Self::Error>;
+}
+
+/// A pin that can be turned into a [`FlexPin`].
+pub trait IntoFlexPin: Pin {
+    /// The type of a [`FlexPin`], which is usually the same for all pins of a platform.
+    type FlexPin: FlexPin<Error = Self::Error> + Unpin;
+
+    /// Attempts to turn this pin into a [`FlexPin`] that is in the same mode as this pin.
+    fn into_flex_pin(self) -> Result<Self::FlexPin, Self::Error>;
+}
+
 /// A virtual pin that is not actually connected to a physical pin.
 ///
 /// The pin will always read a fixed value, can be configured to be in any mode, and will always
@@ -140,7 +434,18 @@ impl InputPin for NoConnect {
         self: pin::Pin<&mut Self>,
         _cx: &mut task::Context<'_>,
     ) -> task::Poll<Result<bool, Self::Error>> {
-        task::Poll::Ready(Ok(false))
+        task::Poll::Ready(Ok(self.0))
+    }
+}
+
+impl EdgeInputPin for NoConnect {
+    fn poll_wait_for_edge(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        _edge: Edge,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        // The level of this pin never changes, so there will never be an edge to report
+        task::Poll::Pending
     }
 }
 
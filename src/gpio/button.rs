@@ -0,0 +1,169 @@
+//! Defines a stream of clicks, double-clicks and long presses of a button.
+use crate::time;
+use crate::timer;
+use core::pin;
+use core::task;
+
+/// Something that a user did with a [`Button`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ButtonEvent {
+    /// The button was pressed and released once.
+    Click,
+    /// The button was pressed a second time shortly after a click.
+    DoubleClick,
+    /// The button has been held down for a while.
+    LongPress,
+}
+
+/// A stream of [`ButtonEvent`]s, detected from the level changes of an input pin.
+///
+/// The button is considered pressed while the pin is high, so buttons that pull their pin low
+/// should be wrapped in an [`Inverted`](super::Inverted) pin.  The pin should usually be
+/// [`Debounced`](super::Debounced) too, since every bounce would otherwise count as a separate
+/// press.
+///
+/// A click is only reported once the double-click window has passed without a second press, so
+/// that a double-click doesn't also result in a click.  A long press is reported as soon as the
+/// button has been held long enough, without waiting for it to be released.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Button<P, T> {
+    pin: P,
+    timer: T,
+    long_press_ticks: u32,
+    double_click_ticks: u32,
+    state: State,
+    restart: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum State {
+    Idle,
+    Pressed(u32),
+    Released(u32),
+    Held,
+}
+
+/// The time between two ticks of the timer of a [`Button`], in milliseconds.
+const RESOLUTION_MILLIS: u32 = 10;
+
+impl<P, T> Button<P, T>
+where
+    P: super::EdgeInputPin + Unpin,
+    T: timer::Timer<Error = P::Error> + Unpin,
+{
+    /// Creates a new button stream.
+    ///
+    /// A press that lasts at least `long_press` is reported as a [`ButtonEvent::LongPress`], and a
+    /// second press within `double_click` of releasing the button as a
+    /// [`ButtonEvent::DoubleClick`].  The `timer` is re-configured into a periodic timer that
+    /// measures both durations with a resolution of 10 milliseconds.
+    pub fn new<U>(
+        pin: P,
+        timer: U,
+        long_press: time::Duration,
+        double_click: time::Duration,
+    ) -> Result<Self, P::Error>
+    where
+        U: timer::IntoPeriodicTimer<PeriodicTimer = T, Error = P::Error>,
+    {
+        let timer =
+            timer.into_periodic_timer(time::Rate::from_hz(1_000.0 / RESOLUTION_MILLIS as f32))?;
+        let long_press_ticks = ticks(long_press);
+        let double_click_ticks = ticks(double_click);
+        let state = State::Idle;
+        let restart = false;
+        Ok(Button {
+            pin,
+            timer,
+            long_press_ticks,
+            double_click_ticks,
+            state,
+            restart,
+        })
+    }
+}
+
+impl<P, T> Button<P, T> {
+    /// Consumes this button, returning the underlying pin and timer.
+    pub fn into_inner(self) -> (P, T) {
+        (self.pin, self.timer)
+    }
+}
+
+impl<P, T> futures::stream::Stream for Button<P, T>
+where
+    P: super::EdgeInputPin + Unpin,
+    T: timer::Timer<Error = P::Error> + Unpin,
+{
+    type Item = Result<ButtonEvent, P::Error>;
+
+    fn poll_next(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.restart {
+                futures::ready!(pin::Pin::new(&mut this.timer).poll_start(cx))?;
+                this.restart = false;
+            }
+
+            match this.state {
+                State::Idle => {
+                    futures::ready!(
+                        pin::Pin::new(&mut this.pin).poll_wait_for_edge(cx, super::Edge::Rising)
+                    )?;
+                    this.state = State::Pressed(0);
+                    this.restart = true;
+                }
+                State::Pressed(ticks) => {
+                    if let task::Poll::Ready(result) =
+                        pin::Pin::new(&mut this.pin).poll_wait_for_edge(cx, super::Edge::Falling)
+                    {
+                        result?;
+                        this.state = State::Released(0);
+                        this.restart = true;
+                        continue;
+                    }
+
+                    futures::ready!(pin::Pin::new(&mut this.timer).poll_tick(cx))?;
+                    if ticks + 1 >= this.long_press_ticks {
+                        this.state = State::Held;
+                        return task::Poll::Ready(Some(Ok(ButtonEvent::LongPress)));
+                    }
+                    this.state = State::Pressed(ticks + 1);
+                }
+                State::Released(ticks) => {
+                    if let task::Poll::Ready(result) =
+                        pin::Pin::new(&mut this.pin).poll_wait_for_edge(cx, super::Edge::Rising)
+                    {
+                        result?;
+                        this.state = State::Held;
+                        return task::Poll::Ready(Some(Ok(ButtonEvent::DoubleClick)));
+                    }
+
+                    futures::ready!(pin::Pin::new(&mut this.timer).poll_tick(cx))?;
+                    if ticks + 1 >= this.double_click_ticks {
+                        this.state = State::Idle;
+                        return task::Poll::Ready(Some(Ok(ButtonEvent::Click)));
+                    }
+                    this.state = State::Released(ticks + 1);
+                }
+                State::Held => {
+                    futures::ready!(
+                        pin::Pin::new(&mut this.pin).poll_wait_for_edge(cx, super::Edge::Falling)
+                    )?;
+                    this.state = State::Idle;
+                }
+            }
+        }
+    }
+}
+
+/// The number of timer ticks that add up to at least the specified duration.
+fn ticks(duration: time::Duration) -> u32 {
+    let resolution = time::Duration::from_millis(RESOLUTION_MILLIS).as_nanos();
+    duration.as_nanos().saturating_add(resolution - 1) / resolution
+}
@@ -0,0 +1,40 @@
+//! Defines streams of level changes of a GPIO pin.
+use core::pin;
+use core::task;
+
+/// A stream of the levels of a GPIO pin, yielding a new level every time that the pin changes.
+///
+/// The stream never ends.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Changes<'a, A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    pin: &'a mut A,
+}
+
+/// Creates a new [`Changes`] stream for the provided GPIO pin.
+pub fn changes<A>(pin: &mut A) -> Changes<A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    Changes { pin }
+}
+
+impl<A> futures::stream::Stream for Changes<'_, A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    type Item = Result<bool, A::Error>;
+
+    fn poll_next(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        pin::Pin::new(&mut *this.pin)
+            .poll_wait_for_edge(cx, super::Edge::Any)
+            .map(Some)
+    }
+}
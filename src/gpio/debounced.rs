@@ -0,0 +1,162 @@
+//! Defines an input pin adapter that filters out bounces.
+use crate::time;
+use crate::timer;
+use core::pin;
+use core::task;
+
+/// An input pin that only reports a level once it has been stable for a while.
+///
+/// Mechanical switches and buttons don't change cleanly, but bounce between levels for a few
+/// milliseconds.  This adapter uses a timer to wait until the underlying pin has stopped changing
+/// for a configured duration before reporting the new level, both through
+/// [`InputPin`](super::InputPin) and [`EdgeInputPin`](super::EdgeInputPin).
+///
+/// Reading the pin returns the last stable level right away, even while the pin is bouncing.  Only
+/// the very first read waits for the pin to settle.  Reads don't affect which edges are reported, so
+/// an edge is still reported after its new level has been read.
+#[derive(Debug)]
+pub struct Debounced<P, T> {
+    pin: P,
+    timer: T,
+    level: Option<bool>,
+    candidate: Option<bool>,
+    restart: bool,
+    last: Option<bool>,
+}
+
+impl<P, T> Debounced<P, T>
+where
+    P: super::EdgeInputPin + Unpin,
+    T: timer::Timer<Error = P::Error> + Unpin,
+{
+    /// Creates a new debounced pin, that reports a level once `pin` has been at it for the
+    /// duration `stable_for`.
+    ///
+    /// The `timer` is re-configured into a oneshot timer that is used to measure the duration.
+    pub fn new<U>(pin: P, timer: U, stable_for: time::Duration) -> Result<Self, P::Error>
+    where
+        U: timer::IntoOneshotTimer<OneshotTimer = T, Error = P::Error>,
+    {
+        let timer = timer.into_oneshot_timer(stable_for)?;
+        let level = None;
+        let candidate = None;
+        let restart = false;
+        let last = None;
+        Ok(Debounced {
+            pin,
+            timer,
+            level,
+            candidate,
+            restart,
+            last,
+        })
+    }
+
+    /// Polls until the underlying pin has settled at a level, returning that level.
+    ///
+    /// The level might be the same as the previous one, if the pin bounced back to it.
+    fn poll_settle(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<bool, P::Error>> {
+        loop {
+            if self.restart {
+                futures::ready!(pin::Pin::new(&mut self.timer).poll_start(cx))?;
+                self.restart = false;
+            }
+
+            match self.candidate {
+                None => {
+                    let level = if self.level.is_none() {
+                        futures::ready!(pin::Pin::new(&mut self.pin).poll_get(cx))?
+                    } else {
+                        futures::ready!(
+                            pin::Pin::new(&mut self.pin).poll_wait_for_edge(cx, super::Edge::Any)
+                        )?
+                    };
+                    self.candidate = Some(level);
+                    self.restart = true;
+                }
+                Some(candidate) => {
+                    // Any change while the timer is running means that the pin is still bouncing
+                    if let task::Poll::Ready(level) =
+                        pin::Pin::new(&mut self.pin).poll_wait_for_edge(cx, super::Edge::Any)
+                    {
+                        self.candidate = Some(level?);
+                        self.restart = true;
+                        continue;
+                    }
+
+                    futures::ready!(pin::Pin::new(&mut self.timer).poll_tick(cx))?;
+                    self.candidate = None;
+                    self.level = Some(candidate);
+                    return task::Poll::Ready(Ok(candidate));
+                }
+            }
+        }
+    }
+}
+
+impl<P, T> Debounced<P, T> {
+    /// Returns a reference to the underlying pin.
+    pub fn get_ref(&self) -> &P {
+        &self.pin
+    }
+
+    /// Consumes this debounced pin, returning the underlying pin and timer.
+    pub fn into_inner(self) -> (P, T) {
+        (self.pin, self.timer)
+    }
+}
+
+impl<P, T> super::Pin for Debounced<P, T>
+where
+    P: super::Pin,
+{
+    type Error = P::Error;
+}
+
+impl<P, T> super::InputPin for Debounced<P, T>
+where
+    P: super::EdgeInputPin + Unpin,
+    T: timer::Timer<Error = P::Error> + Unpin,
+{
+    fn poll_get(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        let this = self.get_mut();
+
+        if let task::Poll::Ready(Err(e)) = this.poll_settle(cx) {
+            return task::Poll::Ready(Err(e));
+        }
+
+        match this.level {
+            Some(level) => task::Poll::Ready(Ok(level)),
+            None => task::Poll::Pending,
+        }
+    }
+}
+
+impl<P, T> super::EdgeInputPin for Debounced<P, T>
+where
+    P: super::EdgeInputPin + Unpin,
+    T: timer::Timer<Error = P::Error> + Unpin,
+{
+    fn poll_wait_for_edge(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        edge: super::Edge,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(level) = this.level {
+                if let Some(last) = this.last.replace(level) {
+                    if last != level && edge.matches(level) {
+                        return task::Poll::Ready(Ok(level));
+                    }
+                }
+            }
+
+            futures::ready!(this.poll_settle(cx))?;
+        }
+    }
+}
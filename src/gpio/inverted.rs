@@ -0,0 +1,199 @@
+//! Defines a pin adapter for active-low signals.
+use core::pin;
+use core::task;
+
+/// A pin that flips all levels, for signals that are active when they are low.
+///
+/// Reading a low level from the underlying pin results in a high level and vice versa, and writing
+/// a high level drives the underlying pin low.  This makes it possible to always treat "high" as
+/// "active", for example for LEDs that light up when their pin is driven low, or for chip selects.
+///
+/// Re-configuring the pin through one of the `Into*` traits keeps the inversion, and the initial
+/// level of an output is inverted too.  Pull-up and pull-down resistors are not swapped though,
+/// since they are a property of the circuit rather than of the signal: a button that connects its
+/// pin to ground still needs a pull-up resistor, and then reads high while it is pressed.
+#[derive(Debug)]
+pub struct Inverted<P> {
+    pin: P,
+}
+
+impl<P> Inverted<P> {
+    /// Creates a new inverted pin.
+    pub fn new(pin: P) -> Self {
+        Inverted { pin }
+    }
+
+    /// Returns a reference to the underlying pin.
+    pub fn get_ref(&self) -> &P {
+        &self.pin
+    }
+
+    /// Returns a mutable reference to the underlying pin.
+    ///
+    /// Levels read from or written to the underlying pin directly are not inverted.
+    pub fn get_mut(&mut self) -> &mut P {
+        &mut self.pin
+    }
+
+    /// Consumes this inverted pin, returning the underlying pin.
+    pub fn into_inner(self) -> P {
+        self.pin
+    }
+}
+
+impl<P> super::Pin for Inverted<P>
+where
+    P: super::Pin,
+{
+    type Error = P::Error;
+}
+
+impl<P> super::InputPin for Inverted<P>
+where
+    P: super::InputPin + Unpin,
+{
+    fn poll_get(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        pin::Pin::new(&mut self.get_mut().pin)
+            .poll_get(cx)
+            .map_ok(|high| !high)
+    }
+}
+
+impl<P> super::EdgeInputPin for Inverted<P>
+where
+    P: super::EdgeInputPin + Unpin,
+{
+    fn poll_wait_for_edge(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        edge: super::Edge,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        let edge = match edge {
+            super::Edge::Rising => super::Edge::Falling,
+            super::Edge::Falling => super::Edge::Rising,
+            super::Edge::Any => super::Edge::Any,
+        };
+        pin::Pin::new(&mut self.get_mut().pin)
+            .poll_wait_for_edge(cx, edge)
+            .map_ok(|high| !high)
+    }
+}
+
+impl<P> super::OutputPin for Inverted<P>
+where
+    P: super::OutputPin + Unpin,
+{
+    fn poll_set(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        high: bool,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.get_mut().pin).poll_set(cx, !high)
+    }
+}
+
+impl<P> super::StatefulOutputPin for Inverted<P>
+where
+    P: super::StatefulOutputPin + Unpin,
+{
+    fn poll_is_set_high(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        pin::Pin::new(&mut self.get_mut().pin)
+            .poll_is_set_high(cx)
+            .map_ok(|high| !high)
+    }
+}
+
+impl<P> super::FlexPin for Inverted<P>
+where
+    P: super::FlexPin + Unpin,
+{
+    fn mode(&self) -> super::Mode {
+        self.pin.mode()
+    }
+
+    fn set_mode(&mut self, mode: super::Mode, initial_high: bool) -> Result<(), Self::Error> {
+        self.pin.set_mode(mode, !initial_high)
+    }
+}
+
+impl<P> super::IntoFloatingInputPin for Inverted<P>
+where
+    P: super::IntoFloatingInputPin,
+{
+    type FloatingInputPin = Inverted<P::FloatingInputPin>;
+
+    fn into_floating_input_pin(self) -> Result<Self::FloatingInputPin, Self::Error> {
+        self.pin.into_floating_input_pin().map(Inverted::new)
+    }
+}
+
+impl<P> super::IntoPullUpInputPin for Inverted<P>
+where
+    P: super::IntoPullUpInputPin,
+{
+    type PullUpInputPin = Inverted<P::PullUpInputPin>;
+
+    fn into_pull_up_input_pin(self) -> Result<Self::PullUpInputPin, Self::Error> {
+        self.pin.into_pull_up_input_pin().map(Inverted::new)
+    }
+}
+
+impl<P> super::IntoPullDownInputPin for Inverted<P>
+where
+    P: super::IntoPullDownInputPin,
+{
+    type PullDownInputPin = Inverted<P::PullDownInputPin>;
+
+    fn into_pull_down_input_pin(self) -> Result<Self::PullDownInputPin, Self::Error> {
+        self.pin.into_pull_down_input_pin().map(Inverted::new)
+    }
+}
+
+impl<P> super::IntoOpenDrainOutputPin for Inverted<P>
+where
+    P: super::IntoOpenDrainOutputPin,
+{
+    type OpenDrainOutputPin = Inverted<P::OpenDrainOutputPin>;
+
+    fn into_open_drain_output_pin(
+        self,
+        initial_high: bool,
+    ) -> Result<Self::OpenDrainOutputPin, Self::Error> {
+        self.pin
+            .into_open_drain_output_pin(!initial_high)
+            .map(Inverted::new)
+    }
+}
+
+impl<P> super::IntoPushPullOutputPin for Inverted<P>
+where
+    P: super::IntoPushPullOutputPin,
+{
+    type PushPullOutputPin = Inverted<P::PushPullOutputPin>;
+
+    fn into_push_pull_output_pin(
+        self,
+        initial_high: bool,
+    ) -> Result<Self::PushPullOutputPin, Self::Error> {
+        self.pin
+            .into_push_pull_output_pin(!initial_high)
+            .map(Inverted::new)
+    }
+}
+
+impl<P> super::IntoFlexPin for Inverted<P>
+where
+    P: super::IntoFlexPin,
+{
+    type FlexPin = Inverted<P::FlexPin>;
+
+    fn into_flex_pin(self) -> Result<Self::FlexPin, Self::Error> {
+        self.pin.into_flex_pin().map(Inverted::new)
+    }
+}
@@ -0,0 +1,34 @@
+//! Defines futures for reading back the output level of a GPIO pin.
+use core::future;
+use core::pin;
+use core::task;
+
+/// A future which reads back whether a GPIO pin is set to a high level.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct IsSetHigh<'a, A>
+where
+    A: super::StatefulOutputPin + Unpin + ?Sized,
+{
+    pin: &'a mut A,
+}
+
+/// Creates a new [`IsSetHigh`] for the provided GPIO pin.
+pub fn is_set_high<A>(pin: &mut A) -> IsSetHigh<A>
+where
+    A: super::StatefulOutputPin + Unpin + ?Sized,
+{
+    IsSetHigh { pin }
+}
+
+impl<A> future::Future for IsSetHigh<'_, A>
+where
+    A: super::StatefulOutputPin + Unpin + ?Sized,
+{
+    type Output = Result<bool, A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = &mut *self;
+        pin::Pin::new(&mut *this.pin).poll_is_set_high(cx)
+    }
+}
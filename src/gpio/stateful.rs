@@ -0,0 +1,71 @@
+//! Defines an output pin adapter that remembers the level it was set to.
+use core::pin;
+use core::task;
+
+/// An output pin that remembers the last level that was written to it.
+///
+/// This turns any [`OutputPin`](super::OutputPin) into a
+/// [`StatefulOutputPin`](super::StatefulOutputPin), and therefore also a
+/// [`ToggleableOutputPin`](super::ToggleableOutputPin), for pins that can't read back their output
+/// latch.  The remembered level is only correct as long as the pin is only written through this
+/// adapter.
+#[derive(Debug)]
+pub struct Stateful<P> {
+    pin: P,
+    high: bool,
+}
+
+impl<P> Stateful<P> {
+    /// Creates a new stateful pin, where `pin` is currently set to the level `initial_high`.
+    ///
+    /// This should be the same level that was passed when turning the pin into an output pin.
+    pub fn new(pin: P, initial_high: bool) -> Self {
+        let high = initial_high;
+        Stateful { pin, high }
+    }
+
+    /// Returns a reference to the underlying pin.
+    pub fn get_ref(&self) -> &P {
+        &self.pin
+    }
+
+    /// Consumes this stateful pin, returning the underlying pin.
+    pub fn into_inner(self) -> P {
+        self.pin
+    }
+}
+
+impl<P> super::Pin for Stateful<P>
+where
+    P: super::Pin,
+{
+    type Error = P::Error;
+}
+
+impl<P> super::OutputPin for Stateful<P>
+where
+    P: super::OutputPin + Unpin,
+{
+    fn poll_set(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        high: bool,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        futures::ready!(pin::Pin::new(&mut this.pin).poll_set(cx, high))?;
+        this.high = high;
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl<P> super::StatefulOutputPin for Stateful<P>
+where
+    P: super::OutputPin + Unpin,
+{
+    fn poll_is_set_high(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<bool, Self::Error>> {
+        task::Poll::Ready(Ok(self.high))
+    }
+}
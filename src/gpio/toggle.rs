@@ -0,0 +1,34 @@
+//! Defines futures for toggling a GPIO pin.
+use core::future;
+use core::pin;
+use core::task;
+
+/// A future which switches a GPIO pin to the other level.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Toggle<'a, A>
+where
+    A: super::ToggleableOutputPin + Unpin + ?Sized,
+{
+    pin: &'a mut A,
+}
+
+/// Creates a new [`Toggle`] for the provided GPIO pin.
+pub fn toggle<A>(pin: &mut A) -> Toggle<A>
+where
+    A: super::ToggleableOutputPin + Unpin + ?Sized,
+{
+    Toggle { pin }
+}
+
+impl<A> future::Future for Toggle<'_, A>
+where
+    A: super::ToggleableOutputPin + Unpin + ?Sized,
+{
+    type Output = Result<(), A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = &mut *self;
+        pin::Pin::new(&mut *this.pin).poll_toggle(cx)
+    }
+}
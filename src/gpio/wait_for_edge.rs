@@ -0,0 +1,36 @@
+//! Defines futures for waiting for an edge on a GPIO pin.
+use core::future;
+use core::pin;
+use core::task;
+
+/// A future which waits for an edge on a GPIO pin, resolving to the level of the pin after it.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForEdge<'a, A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    pin: &'a mut A,
+    edge: super::Edge,
+}
+
+/// Creates a new [`WaitForEdge`] for the provided GPIO pin, that, when polled, will wait for the
+/// specified edge.
+pub fn wait_for_edge<A>(pin: &mut A, edge: super::Edge) -> WaitForEdge<A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    WaitForEdge { pin, edge }
+}
+
+impl<A> future::Future for WaitForEdge<'_, A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    type Output = Result<bool, A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = &mut *self;
+        pin::Pin::new(&mut *this.pin).poll_wait_for_edge(cx, this.edge)
+    }
+}
@@ -0,0 +1,50 @@
+//! Defines futures for waiting for a GPIO pin to reach a level.
+use core::future;
+use core::pin;
+use core::task;
+
+/// A future which waits for a GPIO pin to be at a high or low level.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WaitForLevel<'a, A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    pin: &'a mut A,
+    high: bool,
+}
+
+/// Creates a new [`WaitForLevel`] for the provided GPIO pin, that, when polled, will wait until
+/// the pin is at the specified high or low level.
+///
+/// The future resolves immediately if the pin is already at that level.
+pub fn wait_for_level<A>(pin: &mut A, high: bool) -> WaitForLevel<A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    WaitForLevel { pin, high }
+}
+
+impl<A> future::Future for WaitForLevel<'_, A>
+where
+    A: super::EdgeInputPin + Unpin + ?Sized,
+{
+    type Output = Result<(), A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = &mut *self;
+        let mut pin = pin::Pin::new(&mut *this.pin);
+
+        if futures::ready!(pin.as_mut().poll_get(cx))? == this.high {
+            return task::Poll::Ready(Ok(()));
+        }
+
+        let edge = if this.high {
+            super::Edge::Rising
+        } else {
+            super::Edge::Falling
+        };
+        futures::ready!(pin.poll_wait_for_edge(cx, edge))?;
+        task::Poll::Ready(Ok(()))
+    }
+}
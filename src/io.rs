@@ -9,12 +9,68 @@ use core::fmt;
 use core::pin;
 use core::task;
 
+pub mod buf_reader;
+pub mod buf_writer;
+pub mod chain;
 pub mod close;
+#[cfg(feature = "std")]
+pub mod compat;
+pub mod copy;
+pub mod copy_buf;
+pub mod crc;
+pub mod crc_reader;
+pub mod crc_writer;
+pub mod cursor;
+pub mod duplex;
+pub mod error;
 pub mod flush;
+pub mod inspect;
+pub mod limit;
+pub mod map_err;
+pub mod num;
 pub mod read;
 pub mod read_exact;
+pub mod read_line;
+pub mod read_num;
+pub mod read_until;
+pub mod read_vectored;
+pub mod ring;
+pub mod skip_until;
+pub mod slice;
+pub mod split;
+pub mod take;
+pub mod util;
+pub mod verify;
 pub mod write;
 pub mod write_all;
+pub mod write_all_vectored;
+pub mod write_fmt;
+pub mod write_num;
+pub mod write_vectored;
+
+pub use buf_reader::BufReader;
+pub use buf_writer::BufWriter;
+pub use copy::copy;
+pub use copy_buf::copy_buf;
+pub use crc_reader::CrcReader;
+pub use crc_writer::CrcWriter;
+pub use cursor::Cursor;
+pub use duplex::duplex;
+pub use duplex::DuplexStream;
+pub use duplex::Pipe;
+pub use error::Error;
+pub use error::ErrorKind;
+pub use ring::Ring;
+pub use slice::IoSlice;
+pub use slice::IoSliceMut;
+pub use split::split;
+pub use split::Split;
+pub use util::empty;
+pub use util::repeat;
+pub use util::sink;
+pub use util::Empty;
+pub use util::Repeat;
+pub use util::Sink;
 
 pub trait Read: fmt::Debug {
     type Error: ReadError;
@@ -24,9 +80,56 @@ pub trait Read: fmt::Debug {
         cx: &mut task::Context<'_>,
         buffer: &mut [u8],
     ) -> task::Poll<Result<usize, Self::Error>>;
+
+    /// Reads data into a list of buffers, filling them in order.
+    ///
+    /// The default implementation reads into the first non-empty buffer only.  Readers that can
+    /// scatter data into several buffers in one transfer, like DMA-backed peripherals, should
+    /// override it.
+    fn poll_read_vectored(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffers: &mut [IoSliceMut<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let buffer = buffers
+            .iter_mut()
+            .find(|buffer| !buffer.is_empty())
+            .map_or(&mut [][..], |buffer| &mut **buffer);
+        self.poll_read(cx, buffer)
+    }
+}
+
+/// A reader that has an internal buffer, which makes it possible to look at data before deciding
+/// how much of it to consume.
+pub trait BufRead: Read {
+    /// Returns the contents of the internal buffer, filling it with more data from the underlying
+    /// reader if it is empty.
+    ///
+    /// An empty slice is returned once the underlying reader has reached its end.
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<&[u8], Self::Error>>;
+
+    /// Marks `amount` bytes of the internal buffer as consumed, so that they won't be returned by
+    /// `poll_fill_buf` or `poll_read` again.
+    fn consume(self: pin::Pin<&mut Self>, amount: usize);
+}
+
+/// Behavior shared by the errors of all readers and writers, which makes it possible for generic
+/// code to find out what went wrong, for example to decide whether to retry an operation.
+pub trait IoError: fmt::Debug {
+    /// The kind of this error.
+    fn kind(&self) -> ErrorKind;
+
+    /// Creates an error of kind [`ErrorKind::TimedOut`], for wrappers that give up on operations
+    /// that take too long.
+    fn timed_out() -> Self
+    where
+        Self: Sized;
 }
 
-pub trait ReadError: fmt::Debug {
+pub trait ReadError: IoError {
     fn eof() -> Self;
 }
 
@@ -39,6 +142,23 @@ pub trait Write: fmt::Debug {
         bytes: &[u8],
     ) -> task::Poll<Result<usize, Self::Error>>;
 
+    /// Writes data from a list of buffers, taking them in order.
+    ///
+    /// The default implementation writes from the first non-empty buffer only.  Writers that can
+    /// gather data from several buffers in one transfer, like DMA-backed peripherals, should
+    /// override it.
+    fn poll_write_vectored(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffers: &[IoSlice<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let bytes = buffers
+            .iter()
+            .find(|buffer| !buffer.is_empty())
+            .map_or(&[][..], |buffer| &**buffer);
+        self.poll_write(cx, bytes)
+    }
+
     fn poll_flush(
         self: pin::Pin<&mut Self>,
         cx: &mut task::Context<'_>,
@@ -61,6 +181,14 @@ impl<A: ?Sized + Write + Unpin> Write for &mut A {
         pin::Pin::new(&mut **self).poll_write(cx, bytes)
     }
 
+    fn poll_write_vectored(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffers: &[IoSlice<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        pin::Pin::new(&mut **self).poll_write_vectored(cx, buffers)
+    }
+
     fn poll_flush(
         mut self: pin::Pin<&mut Self>,
         cx: &mut task::Context<'_>,
@@ -76,10 +204,174 @@ impl<A: ?Sized + Write + Unpin> Write for &mut A {
     }
 }
 
-pub trait WriteError: fmt::Debug {
+pub trait WriteError: IoError {
     fn write_zero() -> Self;
 }
 
+/// An object-safe version of [`Read`], with [`ErrorKind`] as its fixed error type.
+///
+/// This is implemented for every [`Read`] that is `Unpin`, and `dyn DynRead` implements [`Read`]
+/// in turn, so readers of different types can be stored side by side as `&mut dyn DynRead` and
+/// still be used with [`ReadExt`].
+pub trait DynRead: fmt::Debug + Unpin {
+    /// Polls a read into `buffer`, like [`Read::poll_read`].
+    fn poll_read(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, ErrorKind>>;
+}
+
+impl<A> DynRead for A
+where
+    A: Read + Unpin,
+{
+    fn poll_read(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, ErrorKind>> {
+        Read::poll_read(pin::Pin::new(self), cx, buffer).map_err(|e| e.kind())
+    }
+}
+
+impl Read for dyn DynRead + '_ {
+    type Error = ErrorKind;
+
+    fn poll_read(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        DynRead::poll_read(self.get_mut(), cx, buffer)
+    }
+}
+
+/// An object-safe version of [`Write`], with [`ErrorKind`] as its fixed error type.
+///
+/// This is implemented for every [`Write`] that is `Unpin`, and `dyn DynWrite` implements
+/// [`Write`] in turn, so writers of different types can be stored side by side as
+/// `&mut dyn DynWrite` and still be used with [`WriteExt`].
+pub trait DynWrite: fmt::Debug + Unpin {
+    /// Polls a write from `bytes`, like [`Write::poll_write`].
+    fn poll_write(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, ErrorKind>>;
+
+    /// Polls a flush, like [`Write::poll_flush`].
+    fn poll_flush(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), ErrorKind>>;
+
+    /// Polls a close, like [`Write::poll_close`].
+    fn poll_close(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), ErrorKind>>;
+}
+
+impl<A> DynWrite for A
+where
+    A: Write + Unpin,
+{
+    fn poll_write(
+        &mut self,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, ErrorKind>> {
+        Write::poll_write(pin::Pin::new(self), cx, bytes).map_err(|e| e.kind())
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), ErrorKind>> {
+        Write::poll_flush(pin::Pin::new(self), cx).map_err(|e| e.kind())
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), ErrorKind>> {
+        Write::poll_close(pin::Pin::new(self), cx).map_err(|e| e.kind())
+    }
+}
+
+impl Write for dyn DynWrite + '_ {
+    type Error = ErrorKind;
+
+    fn poll_write(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        DynWrite::poll_write(self.get_mut(), cx, bytes)
+    }
+
+    fn poll_flush(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        DynWrite::poll_flush(self.get_mut(), cx)
+    }
+
+    fn poll_close(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        DynWrite::poll_close(self.get_mut(), cx)
+    }
+}
+
+macro_rules! read_num_methods {
+    ($($method:ident: $ty:ident $(, $endian:ident)?;)*) => {
+        $(
+            read_num_methods!(@method $method, $ty, $($endian)?);
+        )*
+    };
+    (@method $method:ident, $ty:ident, ) => {
+        #[doc = concat!("Reads a `", stringify!($ty), "`.")]
+        fn $method(&mut self) -> read_num::ReadNum<'_, Self, $ty>
+        where
+            Self: Unpin,
+        {
+            read_num::read_num(self, num::Endian::Little)
+        }
+    };
+    (@method $method:ident, $ty:ident, $endian:ident) => {
+        #[doc = concat!(
+            "Reads a `", stringify!($ty), "` in [`Endian::", stringify!($endian),
+            "`](num::Endian::", stringify!($endian), ") byte order."
+        )]
+        fn $method(&mut self) -> read_num::ReadNum<'_, Self, $ty>
+        where
+            Self: Unpin,
+        {
+            read_num::read_num(self, num::Endian::$endian)
+        }
+    };
+}
+
+macro_rules! write_num_methods {
+    ($($method:ident: $ty:ident $(, $endian:ident)?;)*) => {
+        $(
+            write_num_methods!(@method $method, $ty, $($endian)?);
+        )*
+    };
+    (@method $method:ident, $ty:ident, ) => {
+        #[doc = concat!("Writes a `", stringify!($ty), "`.")]
+        fn $method(&mut self, value: $ty) -> write_num::WriteNum<'_, Self, $ty>
+        where
+            Self: Unpin,
+        {
+            write_num::write_num(self, value, num::Endian::Little)
+        }
+    };
+    (@method $method:ident, $ty:ident, $endian:ident) => {
+        #[doc = concat!(
+            "Writes a `", stringify!($ty), "` in [`Endian::", stringify!($endian),
+            "`](num::Endian::", stringify!($endian), ") byte order."
+        )]
+        fn $method(&mut self, value: $ty) -> write_num::WriteNum<'_, Self, $ty>
+        where
+            Self: Unpin,
+        {
+            write_num::write_num(self, value, num::Endian::$endian)
+        }
+    };
+}
+
 pub trait ReadExt: Read {
     fn read<'a>(&'a mut self, buffer: &'a mut [u8]) -> read::Read<'a, Self>
     where
@@ -94,9 +386,121 @@ pub trait ReadExt: Read {
     {
         read_exact::read_exact(self, buffer)
     }
+
+    fn read_vectored<'a, 'b>(
+        &'a mut self,
+        buffers: &'a mut [IoSliceMut<'b>],
+    ) -> read_vectored::ReadVectored<'a, 'b, Self>
+    where
+        Self: Unpin,
+    {
+        read_vectored::read_vectored(self, buffers)
+    }
+
+    read_num_methods! {
+        read_u8: u8;
+        read_i8: i8;
+        read_u16_le: u16, Little;
+        read_u16_be: u16, Big;
+        read_i16_le: i16, Little;
+        read_i16_be: i16, Big;
+        read_u32_le: u32, Little;
+        read_u32_be: u32, Big;
+        read_i32_le: i32, Little;
+        read_i32_be: i32, Big;
+        read_u64_le: u64, Little;
+        read_u64_be: u64, Big;
+        read_i64_le: i64, Little;
+        read_i64_be: i64, Big;
+        read_f32_le: f32, Little;
+        read_f32_be: f32, Big;
+        read_f64_le: f64, Little;
+        read_f64_be: f64, Big;
+    }
+
+    /// Creates a reader that reads at most `limit` bytes from this reader.
+    fn take(self, limit: u64) -> take::Take<Self>
+    where
+        Self: Sized,
+    {
+        take::take(self, limit)
+    }
+
+    /// Creates a reader that reads from `next` once this reader has reached its end.
+    fn chain<R>(self, next: R) -> chain::Chain<Self, R>
+    where
+        Self: Sized,
+        R: Read<Error = Self::Error>,
+    {
+        chain::chain(self, next)
+    }
+
+    /// Creates a reader that calls `f` with all bytes that are read.
+    ///
+    /// The returned adapter is also a writer if this reader is, in which case `f` also sees the
+    /// bytes that are written.
+    fn inspect_read<F>(self, f: F) -> inspect::Inspect<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&[u8]),
+    {
+        inspect::inspect(self, f)
+    }
+
+    /// Creates a reader that converts all errors of this reader using `f`.
+    ///
+    /// The returned adapter is also a writer if this reader is, so for peripherals that are both,
+    /// calling `uart.map_read_err(f)` converts the errors in both directions.
+    fn map_read_err<F, E>(self, f: F) -> map_err::MapErr<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Error) -> E,
+        E: ReadError,
+    {
+        map_err::map_err(self, f)
+    }
+}
+
+impl<A> ReadExt for A where A: Read + ?Sized {}
+
+pub trait BufReadExt: BufRead {
+    /// Reads bytes into `buffer` until (and including) `delimiter`, returning the number of bytes
+    /// read.
+    ///
+    /// Reading also stops when the buffer is full or the end of the reader is reached, so check
+    /// whether the last byte read is the delimiter to find out whether a whole record was read.
+    fn read_until<'a>(
+        &'a mut self,
+        delimiter: u8,
+        buffer: &'a mut [u8],
+    ) -> read_until::ReadUntil<'a, Self>
+    where
+        Self: Unpin,
+    {
+        read_until::read_until(self, delimiter, buffer)
+    }
+
+    /// Reads a line (including the trailing `\n`, if any) into `buffer`, returning the number of
+    /// bytes read.
+    ///
+    /// This behaves like [`read_until`](BufReadExt::read_until) with a `b'\n'` delimiter.
+    fn read_line<'a>(&'a mut self, buffer: &'a mut [u8]) -> read_line::ReadLine<'a, Self>
+    where
+        Self: Unpin,
+    {
+        read_line::read_line(self, buffer)
+    }
+
+    /// Skips bytes until (and including) `delimiter`, returning the number of bytes skipped.
+    fn skip_until(&mut self, delimiter: u8) -> skip_until::SkipUntil<Self>
+    where
+        Self: Unpin,
+    {
+        skip_until::skip_until(self, delimiter)
+    }
 }
 
-impl<A> ReadExt for A where A: Read {}
+impl<A> BufReadExt for A where A: BufRead + ?Sized {}
 
 pub trait WriteExt: Write {
     fn write<'a>(&'a mut self, bytes: &'a [u8]) -> write::Write<'a, Self>
@@ -113,12 +517,105 @@ pub trait WriteExt: Write {
         write_all::write_all(self, bytes)
     }
 
+    fn write_vectored<'a, 'b>(
+        &'a mut self,
+        buffers: &'a [IoSlice<'b>],
+    ) -> write_vectored::WriteVectored<'a, 'b, Self>
+    where
+        Self: Unpin,
+    {
+        write_vectored::write_vectored(self, buffers)
+    }
+
+    /// Writes all data from a list of buffers, for example a header followed by a payload.
+    ///
+    /// The buffers are advanced past the data that has been written, so their contents are
+    /// unspecified once the returned future completes.
+    fn write_all_vectored<'a, 'b>(
+        &'a mut self,
+        buffers: &'a mut [IoSlice<'b>],
+    ) -> write_all_vectored::WriteAllVectored<'a, 'b, Self>
+    where
+        Self: Unpin,
+    {
+        write_all_vectored::write_all_vectored(self, buffers)
+    }
+
+    /// Writes formatted text, as created by `format_args!`.
+    ///
+    /// The text is formatted into a buffer of [`write_fmt::CAPACITY`] bytes once, and then written
+    /// as the writer accepts it.  Longer text, or a formatting trait implementation that returns an
+    /// error, results in a [`WriteError::write_zero`] error without writing anything.
+    ///
+    /// Usually, this is called through the [`uwrite!`](crate::uwrite) and
+    /// [`uwriteln!`](crate::uwriteln) macros.
+    fn write_fmt<'a>(&'a mut self, arguments: fmt::Arguments<'a>) -> write_fmt::WriteFmt<'a, Self>
+    where
+        Self: Unpin,
+    {
+        write_fmt::write_fmt(self, arguments)
+    }
+
+    write_num_methods! {
+        write_u8: u8;
+        write_i8: i8;
+        write_u16_le: u16, Little;
+        write_u16_be: u16, Big;
+        write_i16_le: i16, Little;
+        write_i16_be: i16, Big;
+        write_u32_le: u32, Little;
+        write_u32_be: u32, Big;
+        write_i32_le: i32, Little;
+        write_i32_be: i32, Big;
+        write_u64_le: u64, Little;
+        write_u64_be: u64, Big;
+        write_i64_le: i64, Little;
+        write_i64_be: i64, Big;
+        write_f32_le: f32, Little;
+        write_f32_be: f32, Big;
+        write_f64_le: f64, Little;
+        write_f64_be: f64, Big;
+    }
+
     fn shutdown(&mut self) -> close::Close<Self>
     where
         Self: Unpin,
     {
         close::close(self)
     }
+
+    /// Creates a writer that writes at most `limit` bytes to this writer.
+    fn limit(self, limit: u64) -> limit::Limit<Self>
+    where
+        Self: Sized,
+    {
+        limit::limit(self, limit)
+    }
+
+    /// Creates a writer that calls `f` with all bytes that are written.
+    ///
+    /// The returned adapter is also a reader if this writer is, in which case `f` also sees the
+    /// bytes that are read.
+    fn inspect_written<F>(self, f: F) -> inspect::Inspect<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&[u8]),
+    {
+        inspect::inspect(self, f)
+    }
+
+    /// Creates a writer that converts all errors of this writer using `f`.
+    ///
+    /// The returned adapter is also a reader if this writer is, so for peripherals that are both,
+    /// calling `uart.map_write_err(f)` converts the errors in both directions.
+    fn map_write_err<F, E>(self, f: F) -> map_err::MapErr<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Error) -> E,
+        E: WriteError,
+    {
+        map_err::map_err(self, f)
+    }
 }
 
-impl<A> WriteExt for A where A: Write {}
+impl<A> WriteExt for A where A: Write + ?Sized {}
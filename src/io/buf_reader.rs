@@ -0,0 +1,181 @@
+use core::cmp;
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// Adds buffering to a reader, using a backing array of `N` bytes.
+///
+/// Reads that are smaller than the buffer are served from the buffer, which is refilled from the
+/// underlying reader with as many bytes as it is willing to give at a time.  Reads that are at
+/// least as large as the buffer bypass it entirely while it is empty.
+pub struct BufReader<R, const N: usize> {
+    reader: R,
+    buffer: [u8; N],
+    position: usize,
+    filled: usize,
+}
+
+impl<R, const N: usize> BufReader<R, N> {
+    /// Creates a new buffered reader with an empty buffer.
+    pub fn new(reader: R) -> Self {
+        let buffer = [0; N];
+        let position = 0;
+        let filled = 0;
+        BufReader {
+            reader,
+            buffer,
+            position,
+            filled,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading directly from the underlying reader will skip past any buffered data.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Returns the data that has been buffered but not read yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[self.position..self.filled]
+    }
+
+    /// Consumes this buffered reader, returning the underlying reader.
+    ///
+    /// Any data that has been buffered but not read yet is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R, const N: usize> super::Read for BufReader<R, N>
+where
+    R: super::Read + Unpin,
+{
+    type Error = R::Error;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+
+        if this.position == this.filled {
+            if buffer.len() >= N {
+                return pin::Pin::new(&mut this.reader).poll_read(cx, buffer);
+            }
+
+            let n =
+                futures::ready!(pin::Pin::new(&mut this.reader).poll_read(cx, &mut this.buffer))?;
+            this.position = 0;
+            this.filled = n;
+        }
+
+        let available = &this.buffer[this.position..this.filled];
+        let n = cmp::min(available.len(), buffer.len());
+        buffer[..n].copy_from_slice(&available[..n]);
+        this.position += n;
+
+        task::Poll::Ready(Ok(n))
+    }
+}
+
+impl<R, const N: usize> super::BufRead for BufReader<R, N>
+where
+    R: super::Read + Unpin,
+{
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<&[u8], Self::Error>> {
+        let this = self.get_mut();
+
+        if this.position == this.filled {
+            let n =
+                futures::ready!(pin::Pin::new(&mut this.reader).poll_read(cx, &mut this.buffer))?;
+            this.position = 0;
+            this.filled = n;
+        }
+
+        task::Poll::Ready(Ok(&this.buffer[this.position..this.filled]))
+    }
+
+    fn consume(mut self: pin::Pin<&mut Self>, amount: usize) {
+        self.position = cmp::min(self.position + amount, self.filled);
+    }
+}
+
+impl<R, const N: usize> fmt::Debug for BufReader<R, N>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufReader")
+            .field("reader", &self.reader)
+            .field("buffered", &(self.filled - self.position))
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BufReader;
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn serves_reads_from_its_buffer() {
+        futures::executor::block_on(async {
+            let mut reads = 0;
+
+            {
+                let cursor = io::Cursor::new(b"abcdefgh").inspect_read(|_: &[u8]| reads += 1);
+                let mut reader = BufReader::<_, 4>::new(cursor);
+                let mut bytes = [0; 2];
+
+                reader.read_exact(&mut bytes).await.unwrap();
+                assert_eq!(&bytes, b"ab");
+                assert_eq!(reader.buffer(), b"cd");
+
+                reader.read_exact(&mut bytes).await.unwrap();
+                assert_eq!(&bytes, b"cd");
+                assert!(reader.buffer().is_empty());
+
+                reader.read_exact(&mut bytes).await.unwrap();
+                assert_eq!(&bytes, b"ef");
+            }
+
+            assert_eq!(reads, 2);
+        });
+    }
+
+    #[test]
+    fn bypasses_the_buffer_for_large_reads() {
+        futures::executor::block_on(async {
+            let mut reads = 0;
+
+            {
+                let cursor = io::Cursor::new(b"abcdefgh").inspect_read(|bytes: &[u8]| {
+                    assert_eq!(bytes, b"abcdef");
+                    reads += 1;
+                });
+                let mut reader = BufReader::<_, 4>::new(cursor);
+                let mut bytes = [0; 6];
+
+                assert_eq!(reader.read(&mut bytes).await.unwrap(), 6);
+                assert_eq!(&bytes, b"abcdef");
+                assert!(reader.buffer().is_empty());
+            }
+
+            assert_eq!(reads, 1);
+        });
+    }
+}
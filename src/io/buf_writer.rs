@@ -0,0 +1,294 @@
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// Adds buffering to a writer, using a backing array of `N` bytes.
+///
+/// Small writes are collected in the buffer, which is written to the underlying writer when it
+/// can't fit any more bytes, or when the writer is flushed or closed.  This batches many small
+/// writes into fewer, larger transfers.  Writes that are at least as large as the buffer bypass it
+/// entirely once it has been flushed.
+///
+/// Any buffered data is lost if the writer is dropped without being flushed or closed.
+pub struct BufWriter<W, const N: usize> {
+    writer: W,
+    buffer: [u8; N],
+    position: usize,
+    filled: usize,
+}
+
+impl<W, const N: usize> BufWriter<W, N> {
+    /// Creates a new buffered writer with an empty buffer.
+    pub fn new(writer: W) -> Self {
+        let buffer = [0; N];
+        let position = 0;
+        let filled = 0;
+        BufWriter {
+            writer,
+            buffer,
+            position,
+            filled,
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the underlying writer will skip ahead of any buffered data.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Returns the data that has been buffered but not written to the underlying writer yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[self.position..self.filled]
+    }
+
+    /// Consumes this buffered writer, returning the underlying writer.
+    ///
+    /// Any data that has been buffered but not written yet is lost.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, const N: usize> BufWriter<W, N>
+where
+    W: super::Write + Unpin,
+{
+    /// Writes all buffered data to the underlying writer, without flushing it.
+    fn poll_flush_buffer(
+        &mut self,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), W::Error>> {
+        use super::WriteError;
+
+        while self.position < self.filled {
+            let n = futures::ready!(pin::Pin::new(&mut self.writer)
+                .poll_write(cx, &self.buffer[self.position..self.filled]))?;
+            if n == 0 {
+                return task::Poll::Ready(Err(W::Error::write_zero()));
+            }
+            self.position += n;
+        }
+
+        self.position = 0;
+        self.filled = 0;
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl<W, const N: usize> super::Write for BufWriter<W, N>
+where
+    W: super::Write + Unpin,
+{
+    type Error = W::Error;
+
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+
+        if this.filled + bytes.len() > N {
+            futures::ready!(this.poll_flush_buffer(cx))?;
+        }
+
+        if bytes.len() >= N {
+            pin::Pin::new(&mut this.writer).poll_write(cx, bytes)
+        } else {
+            this.buffer[this.filled..this.filled + bytes.len()].copy_from_slice(bytes);
+            this.filled += bytes.len();
+            task::Poll::Ready(Ok(bytes.len()))
+        }
+    }
+
+    fn poll_write_vectored(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffers: &[super::IoSlice<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        let total = buffers.iter().map(|buffer| buffer.len()).sum::<usize>();
+
+        if this.filled + total > N {
+            futures::ready!(this.poll_flush_buffer(cx))?;
+        }
+
+        if total >= N {
+            pin::Pin::new(&mut this.writer).poll_write_vectored(cx, buffers)
+        } else {
+            for buffer in buffers {
+                this.buffer[this.filled..this.filled + buffer.len()].copy_from_slice(buffer);
+                this.filled += buffer.len();
+            }
+            task::Poll::Ready(Ok(total))
+        }
+    }
+
+    fn poll_flush(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = &mut *self;
+        futures::ready!(this.poll_flush_buffer(cx))?;
+        pin::Pin::new(&mut this.writer).poll_flush(cx)
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = &mut *self;
+        futures::ready!(this.poll_flush_buffer(cx))?;
+        pin::Pin::new(&mut this.writer).poll_close(cx)
+    }
+}
+
+impl<W, const N: usize> fmt::Debug for BufWriter<W, N>
+where
+    W: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufWriter")
+            .field("writer", &self.writer)
+            .field("buffered", &(self.filled - self.position))
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BufWriter;
+    use crate::io;
+    use crate::prelude::*;
+    use core::future;
+    use core::pin;
+    use core::task;
+
+    fn flush<W>(writer: &mut W) -> impl future::Future<Output = Result<(), W::Error>> + '_
+    where
+        W: io::Write + Unpin,
+    {
+        futures::future::poll_fn(move |cx| pin::Pin::new(&mut *writer).poll_flush(cx))
+    }
+
+    #[test]
+    fn batches_small_writes() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 16];
+            let mut writes = 0;
+
+            {
+                let cursor = io::Cursor::new(&mut buffer[..]);
+                let cursor = cursor.inspect_written(|bytes: &[u8]| {
+                    assert_eq!(bytes, b"abcdef");
+                    writes += 1;
+                });
+                let mut writer = BufWriter::<_, 8>::new(cursor);
+
+                writer.write_all(b"ab").await.unwrap();
+                writer.write_all(b"cd").await.unwrap();
+                writer.write_all(b"ef").await.unwrap();
+                assert_eq!(writer.buffer(), b"abcdef");
+                assert_eq!(writer.get_ref().get_ref().position(), 0);
+
+                flush(&mut writer).await.unwrap();
+                assert!(writer.buffer().is_empty());
+            }
+
+            assert_eq!(writes, 1);
+            assert_eq!(&buffer[..6], b"abcdef");
+        });
+    }
+
+    #[test]
+    fn flushes_when_the_next_write_does_not_fit() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 16];
+            let mut writer = BufWriter::<_, 8>::new(io::Cursor::new(&mut buffer[..]));
+
+            writer.write_all(b"abcdef").await.unwrap();
+            writer.write_all(b"ghij").await.unwrap();
+            assert_eq!(writer.get_ref().position(), 6);
+            assert_eq!(writer.buffer(), b"ghij");
+
+            writer.shutdown().await.unwrap();
+            assert_eq!(writer.get_ref().position(), 10);
+            assert_eq!(&buffer[..10], b"abcdefghij");
+        });
+    }
+
+    #[test]
+    fn bypasses_the_buffer_for_large_writes() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 16];
+            let mut writes = [0; 2];
+            let mut len = 0;
+
+            {
+                let cursor = io::Cursor::new(&mut buffer[..]);
+                let cursor = cursor.inspect_written(|bytes: &[u8]| {
+                    writes[len] = bytes.len();
+                    len += 1;
+                });
+                let mut writer = BufWriter::<_, 4>::new(cursor);
+
+                writer.write_all(b"ab").await.unwrap();
+                assert_eq!(writer.write(b"cdef").await.unwrap(), 4);
+                assert!(writer.buffer().is_empty());
+            }
+
+            // The buffered bytes are written first, so that the order is kept
+            assert_eq!(writes, [2, 4]);
+            assert_eq!(&buffer[..6], b"abcdef");
+        });
+    }
+
+    #[test]
+    fn flushes_the_buffer_on_close() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 16];
+            let mut writer = BufWriter::<_, 8>::new(io::Cursor::new(&mut buffer[..]));
+
+            writer.write_all(b"abc").await.unwrap();
+            writer.shutdown().await.unwrap();
+
+            assert!(writer.buffer().is_empty());
+            assert_eq!(writer.get_ref().position(), 3);
+            assert_eq!(&buffer[..3], b"abc");
+        });
+    }
+
+    #[test]
+    fn resumes_after_a_partial_write() {
+        let mut pipe = io::Pipe::<4>::new();
+        let (first, mut second) = io::duplex(&mut pipe);
+        let mut writer = BufWriter::<_, 8>::new(first);
+        let mut cx = task::Context::from_waker(futures::task::noop_waker_ref());
+
+        futures::executor::block_on(writer.write_all(b"abcdef")).unwrap();
+
+        // The pipe only takes four bytes, so the flush has to wait for the other end
+        let poll = io::Write::poll_flush(pin::Pin::new(&mut writer), &mut cx);
+        assert!(poll.is_pending());
+        assert_eq!(writer.buffer(), b"ef");
+
+        let mut bytes = [0; 8];
+        let n = futures::executor::block_on(second.read(&mut bytes)).unwrap();
+        assert_eq!(&bytes[..n], b"abcd");
+
+        let poll = io::Write::poll_flush(pin::Pin::new(&mut writer), &mut cx);
+        assert!(matches!(poll, task::Poll::Ready(Ok(()))));
+        assert!(writer.buffer().is_empty());
+
+        let n = futures::executor::block_on(second.read(&mut bytes)).unwrap();
+        assert_eq!(&bytes[..n], b"ef");
+    }
+}
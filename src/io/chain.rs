@@ -0,0 +1,96 @@
+use core::pin;
+use core::task;
+
+/// A reader that reads everything from a first reader, and then everything from a second one.
+#[derive(Debug)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+    done_first: bool,
+}
+
+pub fn chain<A, B>(first: A, second: B) -> Chain<A, B>
+where
+    A: super::Read,
+    B: super::Read<Error = A::Error>,
+{
+    let done_first = false;
+    Chain {
+        first,
+        second,
+        done_first,
+    }
+}
+
+impl<A, B> Chain<A, B> {
+    /// Returns references to the underlying readers.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Returns mutable references to the underlying readers.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Consumes this adapter, returning the underlying readers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> super::Read for Chain<A, B>
+where
+    A: super::Read + Unpin,
+    B: super::Read<Error = A::Error> + Unpin,
+{
+    type Error = A::Error;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        if !this.done_first {
+            let n = futures::ready!(pin::Pin::new(&mut this.first).poll_read(cx, buffer))?;
+            if n > 0 || buffer.is_empty() {
+                return task::Poll::Ready(Ok(n));
+            }
+            this.done_first = true;
+        }
+
+        pin::Pin::new(&mut this.second).poll_read(cx, buffer)
+    }
+}
+
+impl<A, B> super::BufRead for Chain<A, B>
+where
+    A: super::BufRead + Unpin,
+    B: super::BufRead<Error = A::Error> + Unpin,
+{
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<&[u8], Self::Error>> {
+        let this = self.get_mut();
+        if !this.done_first {
+            let available = futures::ready!(pin::Pin::new(&mut this.first).poll_fill_buf(cx))?;
+            if !available.is_empty() {
+                return task::Poll::Ready(Ok(available));
+            }
+            this.done_first = true;
+        }
+
+        pin::Pin::new(&mut this.second).poll_fill_buf(cx)
+    }
+
+    fn consume(mut self: pin::Pin<&mut Self>, amount: usize) {
+        let this = &mut *self;
+        if this.done_first {
+            pin::Pin::new(&mut this.second).consume(amount)
+        } else {
+            pin::Pin::new(&mut this.first).consume(amount)
+        }
+    }
+}
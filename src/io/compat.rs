@@ -0,0 +1,239 @@
+//! Adapters between the IO traits of this crate and the ones of [`futures::io`].
+//!
+//! [`FromFutures`] makes a `futures::io::AsyncRead`/`AsyncWrite` (like a socket or a pipe) usable
+//! as an [`io::Read`](super::Read)/[`io::Write`](super::Write), and [`IntoFutures`] does the
+//! reverse, so that e.g. protocol code can be tested against real devices on Linux, and
+//! `futures::io` codecs can be used on top of peripherals.
+//!
+//! This module is only available with the `std` feature.
+use core::fmt;
+use core::pin;
+use core::task;
+use futures::io as futures_io;
+
+/// Makes a `futures::io::AsyncRead`/`AsyncWrite` usable as an [`io::Read`](super::Read)/
+/// [`io::Write`](super::Write).
+#[derive(Debug)]
+pub struct FromFutures<T> {
+    inner: T,
+}
+
+/// Makes an [`io::Read`](super::Read)/[`io::Write`](super::Write) usable as a
+/// `futures::io::AsyncRead`/`AsyncWrite`.
+///
+/// Errors are converted into `futures::io::Error`s of the closest matching kind, that carry the
+/// `Debug` representation of the original error.
+#[derive(Debug)]
+pub struct IntoFutures<T> {
+    inner: T,
+}
+
+impl<T> FromFutures<T> {
+    /// Wraps the specified `futures::io` reader or writer.
+    pub fn new(inner: T) -> Self {
+        FromFutures { inner }
+    }
+
+    /// Returns a reference to the underlying reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader or writer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this adapter, returning the underlying reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> IntoFutures<T> {
+    /// Wraps the specified reader or writer.
+    pub fn new(inner: T) -> Self {
+        IntoFutures { inner }
+    }
+
+    /// Returns a reference to the underlying reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader or writer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this adapter, returning the underlying reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl super::IoError for futures_io::Error {
+    fn kind(&self) -> super::ErrorKind {
+        match futures_io::Error::kind(self) {
+            futures_io::ErrorKind::UnexpectedEof => super::ErrorKind::UnexpectedEof,
+            futures_io::ErrorKind::WriteZero => super::ErrorKind::WriteZero,
+            futures_io::ErrorKind::TimedOut => super::ErrorKind::TimedOut,
+            _ => super::ErrorKind::Other,
+        }
+    }
+
+    fn timed_out() -> Self {
+        futures_io::ErrorKind::TimedOut.into()
+    }
+}
+
+impl super::ReadError for futures_io::Error {
+    fn eof() -> Self {
+        futures_io::ErrorKind::UnexpectedEof.into()
+    }
+}
+
+impl super::WriteError for futures_io::Error {
+    fn write_zero() -> Self {
+        futures_io::ErrorKind::WriteZero.into()
+    }
+}
+
+impl<T> super::Read for FromFutures<T>
+where
+    T: futures_io::AsyncRead + Unpin + fmt::Debug,
+{
+    type Error = futures_io::Error;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        pin::Pin::new(&mut self.inner).poll_read(cx, buffer)
+    }
+}
+
+impl<T> super::BufRead for FromFutures<T>
+where
+    T: futures_io::AsyncBufRead + Unpin + fmt::Debug,
+{
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<&[u8], Self::Error>> {
+        pin::Pin::new(&mut self.get_mut().inner).poll_fill_buf(cx)
+    }
+
+    fn consume(mut self: pin::Pin<&mut Self>, amount: usize) {
+        pin::Pin::new(&mut self.inner).consume(amount)
+    }
+}
+
+impl<T> super::Write for FromFutures<T>
+where
+    T: futures_io::AsyncWrite + Unpin + fmt::Debug,
+{
+    type Error = futures_io::Error;
+
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        pin::Pin::new(&mut self.inner).poll_write(cx, bytes)
+    }
+
+    fn poll_flush(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+impl<T> futures_io::AsyncRead for IntoFutures<T>
+where
+    T: super::Read + Unpin,
+{
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<futures_io::Result<usize>> {
+        pin::Pin::new(&mut self.inner)
+            .poll_read(cx, buffer)
+            .map_err(into_futures_error)
+    }
+}
+
+impl<T> futures_io::AsyncBufRead for IntoFutures<T>
+where
+    T: super::BufRead + Unpin,
+{
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<futures_io::Result<&[u8]>> {
+        pin::Pin::new(&mut self.get_mut().inner)
+            .poll_fill_buf(cx)
+            .map_err(into_futures_error)
+    }
+
+    fn consume(mut self: pin::Pin<&mut Self>, amount: usize) {
+        pin::Pin::new(&mut self.inner).consume(amount)
+    }
+}
+
+impl<T> futures_io::AsyncWrite for IntoFutures<T>
+where
+    T: super::Write + Unpin,
+{
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<futures_io::Result<usize>> {
+        pin::Pin::new(&mut self.inner)
+            .poll_write(cx, bytes)
+            .map_err(into_futures_error)
+    }
+
+    fn poll_flush(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<futures_io::Result<()>> {
+        pin::Pin::new(&mut self.inner)
+            .poll_flush(cx)
+            .map_err(into_futures_error)
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<futures_io::Result<()>> {
+        pin::Pin::new(&mut self.inner)
+            .poll_close(cx)
+            .map_err(into_futures_error)
+    }
+}
+
+fn into_futures_error<E>(error: E) -> futures_io::Error
+where
+    E: super::IoError,
+{
+    let kind = match error.kind() {
+        super::ErrorKind::UnexpectedEof => futures_io::ErrorKind::UnexpectedEof,
+        super::ErrorKind::WriteZero => futures_io::ErrorKind::WriteZero,
+        super::ErrorKind::TimedOut => futures_io::ErrorKind::TimedOut,
+        _ => futures_io::ErrorKind::Other,
+    };
+    futures_io::Error::new(kind, std::format!("{:?}", error))
+}
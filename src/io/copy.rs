@@ -0,0 +1,172 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Copy<'a, R, W>
+where
+    R: super::Read<Error = W::Error> + Unpin + ?Sized,
+    W: super::Write + Unpin + ?Sized,
+{
+    reader: &'a mut R,
+    writer: &'a mut W,
+    scratch: &'a mut [u8],
+    position: usize,
+    filled: usize,
+    eof: bool,
+    flush: bool,
+    total: u64,
+}
+
+/// Copies all bytes from `reader` to `writer` until the reader reaches its end, using `scratch` as
+/// an intermediate buffer, and returns the total number of bytes copied.
+///
+/// The reader has reached its end when it reads zero bytes, so nothing is copied if `scratch` is
+/// empty.  A writer that writes zero bytes results in a
+/// [`WriteError::write_zero`](super::WriteError) error.
+pub fn copy<'a, R, W>(reader: &'a mut R, writer: &'a mut W, scratch: &'a mut [u8]) -> Copy<'a, R, W>
+where
+    R: super::Read<Error = W::Error> + Unpin + ?Sized,
+    W: super::Write + Unpin + ?Sized,
+{
+    let position = 0;
+    let filled = 0;
+    let eof = false;
+    let flush = false;
+    let total = 0;
+    Copy {
+        reader,
+        writer,
+        scratch,
+        position,
+        filled,
+        eof,
+        flush,
+        total,
+    }
+}
+
+impl<'a, R, W> Copy<'a, R, W>
+where
+    R: super::Read<Error = W::Error> + Unpin + ?Sized,
+    W: super::Write + Unpin + ?Sized,
+{
+    /// Also flushes the writer once everything has been copied.
+    pub fn and_flush(self) -> Self {
+        let flush = true;
+        Copy { flush, ..self }
+    }
+}
+
+impl<R, W> future::Future for Copy<'_, R, W>
+where
+    R: super::Read<Error = W::Error> + Unpin + ?Sized,
+    W: super::Write + Unpin + ?Sized,
+{
+    type Output = Result<u64, W::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        use super::WriteError;
+
+        let this = &mut *self;
+        loop {
+            if this.position == this.filled && !this.eof {
+                let n = futures::ready!(
+                    pin::Pin::new(&mut *this.reader).poll_read(cx, &mut *this.scratch)
+                )?;
+                this.position = 0;
+                this.filled = n;
+                this.eof = n == 0;
+            }
+
+            while this.position < this.filled {
+                let n = futures::ready!(pin::Pin::new(&mut *this.writer)
+                    .poll_write(cx, &this.scratch[this.position..this.filled]))?;
+                if n == 0 {
+                    return task::Poll::Ready(Err(W::Error::write_zero()));
+                }
+                this.position += n;
+                this.total += n as u64;
+            }
+
+            if this.eof {
+                if this.flush {
+                    futures::ready!(pin::Pin::new(&mut *this.writer).poll_flush(cx))?;
+                }
+                return task::Poll::Ready(Ok(this.total));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn copies_everything_in_several_rounds() {
+        futures::executor::block_on(async {
+            let mut reader = io::Cursor::new(b"hello world");
+            let mut buffer = [0; 16];
+            let mut writer = io::Cursor::new(&mut buffer[..]);
+            let mut scratch = [0; 4];
+
+            let total = io::copy(&mut reader, &mut writer, &mut scratch).await;
+            assert_eq!(total, Ok(11));
+            assert_eq!(writer.position(), 11);
+            assert_eq!(&buffer[..11], b"hello world");
+        });
+    }
+
+    #[test]
+    fn flushes_the_writer_when_asked_to() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 16];
+            let mut writer = io::BufWriter::<_, 8>::new(io::Cursor::new(&mut buffer[..]));
+            let mut scratch = [0; 4];
+
+            let mut reader = io::Cursor::new(b"abc");
+            io::copy(&mut reader, &mut writer, &mut scratch)
+                .await
+                .unwrap();
+            assert_eq!(writer.buffer(), b"abc");
+
+            let mut reader = io::Cursor::new(b"def");
+            io::copy(&mut reader, &mut writer, &mut scratch)
+                .and_flush()
+                .await
+                .unwrap();
+            assert!(writer.buffer().is_empty());
+            assert_eq!(writer.get_ref().position(), 6);
+            assert_eq!(&buffer[..6], b"abcdef");
+        });
+    }
+
+    #[test]
+    fn fails_when_the_writer_writes_zero_bytes() {
+        futures::executor::block_on(async {
+            let mut reader = io::Cursor::new(b"hello world");
+            let mut buffer = [0; 16];
+            let mut writer = io::Cursor::new(&mut buffer[..]).limit(5);
+            let mut scratch = [0; 4];
+
+            let result = io::copy(&mut reader, &mut writer, &mut scratch).await;
+            assert_eq!(result, Err(io::Error::WriteZero));
+            assert_eq!(&buffer[..5], b"hello");
+        });
+    }
+
+    #[test]
+    fn copies_nothing_with_an_empty_scratch_buffer() {
+        futures::executor::block_on(async {
+            let mut reader = io::Cursor::new(b"hello");
+            let mut writer = io::sink();
+
+            let total = io::copy(&mut reader, &mut writer, &mut []).await;
+            assert_eq!(total, Ok(0));
+            assert_eq!(reader.position(), 0);
+        });
+    }
+}
@@ -0,0 +1,132 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CopyBuf<'a, R, W>
+where
+    R: super::BufRead<Error = W::Error> + Unpin + ?Sized,
+    W: super::Write + Unpin + ?Sized,
+{
+    reader: &'a mut R,
+    writer: &'a mut W,
+    flush: bool,
+    total: u64,
+}
+
+/// Copies all bytes from `reader` to `writer` until the reader reaches its end, writing directly
+/// from the reader's internal buffer, and returns the total number of bytes copied.
+///
+/// A writer that writes zero bytes results in a [`WriteError::write_zero`](super::WriteError)
+/// error.
+pub fn copy_buf<'a, R, W>(reader: &'a mut R, writer: &'a mut W) -> CopyBuf<'a, R, W>
+where
+    R: super::BufRead<Error = W::Error> + Unpin + ?Sized,
+    W: super::Write + Unpin + ?Sized,
+{
+    let flush = false;
+    let total = 0;
+    CopyBuf {
+        reader,
+        writer,
+        flush,
+        total,
+    }
+}
+
+impl<'a, R, W> CopyBuf<'a, R, W>
+where
+    R: super::BufRead<Error = W::Error> + Unpin + ?Sized,
+    W: super::Write + Unpin + ?Sized,
+{
+    /// Also flushes the writer once everything has been copied.
+    pub fn and_flush(self) -> Self {
+        let flush = true;
+        CopyBuf { flush, ..self }
+    }
+}
+
+impl<R, W> future::Future for CopyBuf<'_, R, W>
+where
+    R: super::BufRead<Error = W::Error> + Unpin + ?Sized,
+    W: super::Write + Unpin + ?Sized,
+{
+    type Output = Result<u64, W::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        use super::WriteError;
+
+        let this = &mut *self;
+        loop {
+            let available = futures::ready!(pin::Pin::new(&mut *this.reader).poll_fill_buf(cx))?;
+            if available.is_empty() {
+                if this.flush {
+                    futures::ready!(pin::Pin::new(&mut *this.writer).poll_flush(cx))?;
+                }
+                return task::Poll::Ready(Ok(this.total));
+            }
+
+            let n = futures::ready!(pin::Pin::new(&mut *this.writer).poll_write(cx, available))?;
+            if n == 0 {
+                return task::Poll::Ready(Err(W::Error::write_zero()));
+            }
+            pin::Pin::new(&mut *this.reader).consume(n);
+            this.total += n as u64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn copies_everything_in_several_rounds() {
+        futures::executor::block_on(async {
+            let mut reader = io::BufReader::<_, 4>::new(io::Cursor::new(b"hello world"));
+            let mut buffer = [0; 16];
+            let mut writer = io::Cursor::new(&mut buffer[..]);
+
+            let total = io::copy_buf(&mut reader, &mut writer).await;
+            assert_eq!(total, Ok(11));
+            assert_eq!(writer.position(), 11);
+            assert_eq!(&buffer[..11], b"hello world");
+        });
+    }
+
+    #[test]
+    fn flushes_the_writer_when_asked_to() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 16];
+            let mut writer = io::BufWriter::<_, 8>::new(io::Cursor::new(&mut buffer[..]));
+
+            let mut reader = io::Cursor::new(b"abc");
+            io::copy_buf(&mut reader, &mut writer).await.unwrap();
+            assert_eq!(writer.buffer(), b"abc");
+
+            let mut reader = io::Cursor::new(b"def");
+            io::copy_buf(&mut reader, &mut writer)
+                .and_flush()
+                .await
+                .unwrap();
+            assert!(writer.buffer().is_empty());
+            assert_eq!(writer.get_ref().position(), 6);
+            assert_eq!(&buffer[..6], b"abcdef");
+        });
+    }
+
+    #[test]
+    fn fails_when_the_writer_writes_zero_bytes() {
+        futures::executor::block_on(async {
+            let mut reader = io::Cursor::new(b"hello world");
+            let mut buffer = [0; 16];
+            let mut writer = io::Cursor::new(&mut buffer[..]).limit(5);
+
+            let result = io::copy_buf(&mut reader, &mut writer).await;
+            assert_eq!(result, Err(io::Error::WriteZero));
+            assert_eq!(reader.position(), 5);
+        });
+    }
+}
@@ -0,0 +1,280 @@
+//! Cyclic redundancy checks, as computed by [`CrcReader`](super::CrcReader) and
+//! [`CrcWriter`](super::CrcWriter).
+//!
+//! [`Crc8`], [`Crc16`] and [`Crc32`] implement any CRC of their width that is described by a set
+//! of [`Params`], either bit by bit, or using a 256-entry lookup table that is faster but takes up
+//! more memory.  The lookup table can be computed at compile time:
+//!
+//! ```ignore
+//! static CRC: Crc32 = Crc32::table(crc::CRC_32_ISO_HDLC);
+//! ```
+use core::fmt;
+
+/// The parameters that describe a CRC algorithm.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Params<T> {
+    /// The generator polynomial, without its highest term.
+    pub poly: T,
+    /// The initial value of the CRC register.
+    pub init: T,
+    /// Whether bytes are processed least significant bit first, which also means that the final
+    /// value is reflected.
+    pub reflected: bool,
+    /// The value that the final value is XOR-ed with.
+    pub xorout: T,
+}
+
+/// A CRC algorithm.
+pub trait Crc {
+    /// The type of the CRC values, for example `u16` for a 16-bit CRC.
+    type Value: super::num::Number + Eq;
+
+    /// The value of the CRC register before any data has been processed.
+    fn init(&self) -> Self::Value;
+
+    /// Updates the CRC register with the specified bytes.
+    fn update(&self, value: Self::Value, bytes: &[u8]) -> Self::Value;
+
+    /// Converts the CRC register into the final CRC value.
+    fn finalize(&self, value: Self::Value) -> Self::Value;
+
+    /// Computes the CRC value of the specified bytes.
+    fn checksum(&self, bytes: &[u8]) -> Self::Value {
+        self.finalize(self.update(self.init(), bytes))
+    }
+}
+
+/// CRC-8/SMBUS, used for the packet error code (PEC) of SMBus.
+pub const CRC_8_SMBUS: Params<u8> = Params {
+    poly: 0x07,
+    init: 0x00,
+    reflected: false,
+    xorout: 0x00,
+};
+
+/// CRC-16/IBM-3740, also known as CRC-16/CCITT-FALSE.
+pub const CRC_16_IBM_3740: Params<u16> = Params {
+    poly: 0x1021,
+    init: 0xffff,
+    reflected: false,
+    xorout: 0x0000,
+};
+
+/// CRC-16/XMODEM, also used by ZMODEM and some bootloaders.
+pub const CRC_16_XMODEM: Params<u16> = Params {
+    poly: 0x1021,
+    init: 0x0000,
+    reflected: false,
+    xorout: 0x0000,
+};
+
+/// CRC-16/MODBUS.
+pub const CRC_16_MODBUS: Params<u16> = Params {
+    poly: 0x8005,
+    init: 0xffff,
+    reflected: true,
+    xorout: 0x0000,
+};
+
+/// CRC-32/ISO-HDLC, the CRC-32 used by Ethernet, zlib and PNG.
+pub const CRC_32_ISO_HDLC: Params<u32> = Params {
+    poly: 0x04c1_1db7,
+    init: 0xffff_ffff,
+    reflected: true,
+    xorout: 0xffff_ffff,
+};
+
+/// CRC-32/ISCSI, also known as CRC-32C.
+pub const CRC_32_ISCSI: Params<u32> = Params {
+    poly: 0x1edc_6f41,
+    init: 0xffff_ffff,
+    reflected: true,
+    xorout: 0xffff_ffff,
+};
+
+macro_rules! crc {
+    ($($name:ident: $ty:ident, $bits:expr;)*) => {
+        $(
+            #[doc = concat!("A ", stringify!($bits), "-bit CRC algorithm.")]
+            #[derive(Clone)]
+            pub struct $name {
+                params: Params<$ty>,
+                table: Option<[$ty; 256]>,
+            }
+
+            // Casting between `u8` and the value type is trivial for `Crc8`
+            #[allow(trivial_numeric_casts)]
+            impl $name {
+                /// Creates an algorithm that processes data bit by bit.
+                pub const fn bitwise(params: Params<$ty>) -> Self {
+                    let table = None;
+                    $name { params, table }
+                }
+
+                /// Creates an algorithm that processes data byte by byte using a lookup table.
+                pub const fn table(params: Params<$ty>) -> Self {
+                    let mut table = [0; 256];
+                    let mut i = 0;
+                    while i < 256 {
+                        table[i] = Self::update_byte(&params, 0, i as u8);
+                        i += 1;
+                    }
+                    let table = Some(table);
+                    $name { params, table }
+                }
+
+                /// The parameters of this algorithm.
+                pub const fn params(&self) -> Params<$ty> {
+                    self.params
+                }
+
+                const fn update_byte(params: &Params<$ty>, mut value: $ty, byte: u8) -> $ty {
+                    let mut bit = 0;
+                    if params.reflected {
+                        let poly = params.poly.reverse_bits();
+                        value ^= byte as $ty;
+                        while bit < 8 {
+                            value = if value & 1 != 0 {
+                                (value >> 1) ^ poly
+                            } else {
+                                value >> 1
+                            };
+                            bit += 1;
+                        }
+                    } else {
+                        value ^= (byte as $ty) << ($bits - 8);
+                        while bit < 8 {
+                            value = if value & (1 << ($bits - 1)) != 0 {
+                                (value << 1) ^ params.poly
+                            } else {
+                                value << 1
+                            };
+                            bit += 1;
+                        }
+                    }
+                    value
+                }
+            }
+
+            #[allow(trivial_numeric_casts)]
+            impl Crc for $name {
+                type Value = $ty;
+
+                fn init(&self) -> Self::Value {
+                    if self.params.reflected {
+                        self.params.init.reverse_bits()
+                    } else {
+                        self.params.init
+                    }
+                }
+
+                fn update(&self, mut value: Self::Value, bytes: &[u8]) -> Self::Value {
+                    match self.table {
+                        Some(ref table) if self.params.reflected => {
+                            for &byte in bytes {
+                                let index = (value as u8 ^ byte) as usize;
+                                value = (u64::from(value) >> 8) as $ty ^ table[index];
+                            }
+                        }
+                        Some(ref table) => {
+                            for &byte in bytes {
+                                let index = ((value >> ($bits - 8)) as u8 ^ byte) as usize;
+                                value = (u64::from(value) << 8) as $ty ^ table[index];
+                            }
+                        }
+                        None => {
+                            for &byte in bytes {
+                                value = Self::update_byte(&self.params, value, byte);
+                            }
+                        }
+                    }
+                    value
+                }
+
+                fn finalize(&self, value: Self::Value) -> Self::Value {
+                    value ^ self.params.xorout
+                }
+            }
+
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_struct(stringify!($name))
+                        .field("params", &self.params)
+                        .field("table", &self.table.is_some())
+                        .finish()
+                }
+            }
+        )*
+    };
+}
+
+crc! {
+    Crc8: u8, 8;
+    Crc16: u16, 16;
+    Crc32: u32, 32;
+}
+
+impl<C> Crc for &C
+where
+    C: Crc + ?Sized,
+{
+    type Value = C::Value;
+
+    fn init(&self) -> Self::Value {
+        (**self).init()
+    }
+
+    fn update(&self, value: Self::Value, bytes: &[u8]) -> Self::Value {
+        (**self).update(value, bytes)
+    }
+
+    fn finalize(&self, value: Self::Value) -> Self::Value {
+        (**self).finalize(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECK: &[u8] = b"123456789";
+
+    static TABLE: Crc32 = Crc32::table(CRC_32_ISO_HDLC);
+
+    #[test]
+    fn computes_8_bit_check_values() {
+        assert_eq!(Crc8::bitwise(CRC_8_SMBUS).checksum(CHECK), 0xf4);
+        assert_eq!(Crc8::table(CRC_8_SMBUS).checksum(CHECK), 0xf4);
+    }
+
+    #[test]
+    fn computes_16_bit_check_values() {
+        assert_eq!(Crc16::bitwise(CRC_16_IBM_3740).checksum(CHECK), 0x29b1);
+        assert_eq!(Crc16::table(CRC_16_IBM_3740).checksum(CHECK), 0x29b1);
+        assert_eq!(Crc16::bitwise(CRC_16_XMODEM).checksum(CHECK), 0x31c3);
+        assert_eq!(Crc16::table(CRC_16_XMODEM).checksum(CHECK), 0x31c3);
+        assert_eq!(Crc16::bitwise(CRC_16_MODBUS).checksum(CHECK), 0x4b37);
+        assert_eq!(Crc16::table(CRC_16_MODBUS).checksum(CHECK), 0x4b37);
+    }
+
+    #[test]
+    fn computes_32_bit_check_values() {
+        assert_eq!(Crc32::bitwise(CRC_32_ISO_HDLC).checksum(CHECK), 0xcbf4_3926);
+        assert_eq!(Crc32::table(CRC_32_ISO_HDLC).checksum(CHECK), 0xcbf4_3926);
+        assert_eq!(Crc32::bitwise(CRC_32_ISCSI).checksum(CHECK), 0xe306_9283);
+        assert_eq!(Crc32::table(CRC_32_ISCSI).checksum(CHECK), 0xe306_9283);
+    }
+
+    #[test]
+    fn computes_tables_at_compile_time() {
+        assert_eq!(TABLE.checksum(CHECK), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn updates_incrementally() {
+        let crc = Crc16::table(CRC_16_MODBUS);
+        let (head, tail) = CHECK.split_at(4);
+        let value = crc.update(crc.update(crc.init(), head), tail);
+        assert_eq!(crc.finalize(value), 0x4b37);
+    }
+}
@@ -0,0 +1,82 @@
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// A reader that computes a CRC of all bytes that are read through it.
+#[derive(Debug)]
+pub struct CrcReader<R, C>
+where
+    C: super::crc::Crc,
+{
+    reader: R,
+    crc: C,
+    value: C::Value,
+}
+
+impl<R, C> CrcReader<R, C>
+where
+    C: super::crc::Crc,
+{
+    /// Creates a new reader that computes a CRC using the specified algorithm.
+    pub fn new(reader: R, crc: C) -> Self {
+        let value = crc.init();
+        CrcReader { reader, crc, value }
+    }
+
+    /// The CRC of all bytes that have been read so far.
+    pub fn value(&self) -> C::Value {
+        self.crc.finalize(self.value)
+    }
+
+    /// Starts computing a new CRC, as if no bytes had been read yet.
+    pub fn reset(&mut self) {
+        self.value = self.crc.init();
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Bytes that are read directly from the underlying reader are not included in the CRC.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes this adapter, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads a checksum in the specified byte order, and checks whether it matches the CRC of all
+    /// bytes that have been read so far.
+    ///
+    /// The checksum itself is not included in the CRC.
+    pub fn verify(&mut self, endian: super::num::Endian) -> super::verify::Verify<'_, R, C>
+    where
+        R: super::Read + Unpin,
+    {
+        super::verify::verify(self, endian)
+    }
+}
+
+impl<R, C> super::Read for CrcReader<R, C>
+where
+    R: super::Read + Unpin,
+    C: super::crc::Crc + Unpin + fmt::Debug,
+{
+    type Error = R::Error;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        let n = futures::ready!(pin::Pin::new(&mut this.reader).poll_read(cx, buffer))?;
+        this.value = this.crc.update(this.value, &buffer[..n]);
+        task::Poll::Ready(Ok(n))
+    }
+}
@@ -0,0 +1,86 @@
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// A writer that computes a CRC of all bytes that are written through it.
+#[derive(Debug)]
+pub struct CrcWriter<W, C>
+where
+    C: super::crc::Crc,
+{
+    writer: W,
+    crc: C,
+    value: C::Value,
+}
+
+impl<W, C> CrcWriter<W, C>
+where
+    C: super::crc::Crc,
+{
+    /// Creates a new writer that computes a CRC using the specified algorithm.
+    pub fn new(writer: W, crc: C) -> Self {
+        let value = crc.init();
+        CrcWriter { writer, crc, value }
+    }
+
+    /// The CRC of all bytes that have been written so far.
+    pub fn value(&self) -> C::Value {
+        self.crc.finalize(self.value)
+    }
+
+    /// Starts computing a new CRC, as if no bytes had been written yet.
+    pub fn reset(&mut self) {
+        self.value = self.crc.init();
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Bytes that are written directly to the underlying writer are not included in the CRC, which
+    /// makes it possible to append the checksum itself.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes this adapter, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, C> super::Write for CrcWriter<W, C>
+where
+    W: super::Write + Unpin,
+    C: super::crc::Crc + Unpin + fmt::Debug,
+{
+    type Error = W::Error;
+
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        let n = futures::ready!(pin::Pin::new(&mut this.writer).poll_write(cx, bytes))?;
+        this.value = this.crc.update(this.value, &bytes[..n]);
+        task::Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.writer).poll_flush(cx)
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.writer).poll_close(cx)
+    }
+}
@@ -0,0 +1,183 @@
+use core::cmp;
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// Reads from and writes to an in-memory byte slice, keeping track of the current position.
+///
+/// Any `T: AsRef<[u8]>` can be read from, like `&[u8]` or `[u8; N]`, and any `T: AsMut<[u8]>`
+/// can be written to, like `&mut [u8]` or `[u8; N]`.  Writing never grows the underlying slice,
+/// so writes past its end write zero bytes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Cursor<T> {
+    inner: T,
+    position: usize,
+}
+
+impl<T> Cursor<T> {
+    /// Creates a new cursor positioned at the start of the specified slice.
+    pub fn new(inner: T) -> Self {
+        let position = 0;
+        Cursor { inner, position }
+    }
+
+    /// The current position of this cursor.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves this cursor to the specified position, which may be past the end of the slice.
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// Returns a reference to the underlying slice.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying slice.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this cursor, returning the underlying slice.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> Cursor<T>
+where
+    T: AsRef<[u8]>,
+{
+    /// The part of the slice after the current position.
+    pub fn remaining_slice(&self) -> &[u8] {
+        let bytes = self.inner.as_ref();
+        &bytes[cmp::min(self.position, bytes.len())..]
+    }
+}
+
+impl<T> super::Read for Cursor<T>
+where
+    T: AsRef<[u8]> + Unpin + fmt::Debug,
+{
+    type Error = super::Error;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let remaining = self.remaining_slice();
+        let n = cmp::min(buffer.len(), remaining.len());
+        buffer[..n].copy_from_slice(&remaining[..n]);
+        self.position += n;
+        task::Poll::Ready(Ok(n))
+    }
+}
+
+impl<T> super::BufRead for Cursor<T>
+where
+    T: AsRef<[u8]> + Unpin + fmt::Debug,
+{
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<&[u8], Self::Error>> {
+        task::Poll::Ready(Ok(self.get_mut().remaining_slice()))
+    }
+
+    fn consume(mut self: pin::Pin<&mut Self>, amount: usize) {
+        self.position += amount;
+    }
+}
+
+impl<T> super::Write for Cursor<T>
+where
+    T: AsMut<[u8]> + Unpin + fmt::Debug,
+{
+    type Error = super::Error;
+
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        let slice = this.inner.as_mut();
+        let start = cmp::min(this.position, slice.len());
+        let n = cmp::min(bytes.len(), slice.len() - start);
+        slice[start..start + n].copy_from_slice(&bytes[..n]);
+        this.position += n;
+        task::Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+    use core::pin;
+    use core::task;
+
+    #[test]
+    fn reads_nothing_past_the_end() {
+        futures::executor::block_on(async {
+            let mut cursor = io::Cursor::new(b"abc");
+            let mut bytes = [0; 4];
+
+            assert_eq!(cursor.read(&mut bytes).await, Ok(3));
+            assert_eq!(&bytes[..3], b"abc");
+            assert_eq!(cursor.read(&mut bytes).await, Ok(0));
+
+            cursor.set_position(10);
+            assert!(cursor.remaining_slice().is_empty());
+            assert_eq!(cursor.read(&mut bytes).await, Ok(0));
+        });
+    }
+
+    #[test]
+    fn writes_nothing_past_the_end() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 4];
+            let mut cursor = io::Cursor::new(&mut buffer[..]);
+
+            assert_eq!(cursor.write(b"abcdef").await, Ok(4));
+            assert_eq!(cursor.write(b"ef").await, Ok(0));
+            assert_eq!(cursor.write_all(b"ef").await, Err(io::Error::WriteZero));
+
+            cursor.set_position(10);
+            assert_eq!(cursor.write(b"ef").await, Ok(0));
+            assert_eq!(&buffer, b"abcd");
+        });
+    }
+
+    #[test]
+    fn consumes_what_was_filled() {
+        let mut cursor = io::Cursor::new(b"hello");
+        let mut cx = task::Context::from_waker(futures::task::noop_waker_ref());
+
+        let poll = io::BufRead::poll_fill_buf(pin::Pin::new(&mut cursor), &mut cx);
+        assert_eq!(poll, task::Poll::Ready(Ok(&b"hello"[..])));
+        io::BufRead::consume(pin::Pin::new(&mut cursor), 2);
+        assert_eq!(cursor.position(), 2);
+
+        let poll = io::BufRead::poll_fill_buf(pin::Pin::new(&mut cursor), &mut cx);
+        assert_eq!(poll, task::Poll::Ready(Ok(&b"llo"[..])));
+    }
+}
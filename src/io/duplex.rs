@@ -0,0 +1,325 @@
+use core::cell;
+use core::cmp;
+use core::pin;
+use core::task;
+
+/// The shared storage of a pair of connected [`DuplexStream`]s, with room for `N` bytes in each
+/// direction.
+///
+/// The storage has to outlive the streams, and isn't thread-safe, so both streams must be used
+/// from the same thread (usually by the same executor).
+#[derive(Debug)]
+pub struct Pipe<const N: usize> {
+    channels: [cell::RefCell<Channel<N>>; 2],
+}
+
+/// One end of an in-memory loopback connection, created by [`duplex`].
+///
+/// Everything written to one end can be read from the other end.  Writes wait while the other end
+/// has `N` bytes that it hasn't read yet.  Once one end has been closed or dropped, the other end
+/// reads until it has drained the remaining data and then reaches its end; once one end has been
+/// dropped, writes to the other end write zero bytes.
+#[derive(Debug)]
+pub struct DuplexStream<'a, const N: usize> {
+    pipe: &'a Pipe<N>,
+    side: usize,
+}
+
+#[derive(Debug)]
+struct Channel<const N: usize> {
+    buffer: [u8; N],
+    start: usize,
+    len: usize,
+    closed: bool,
+    abandoned: bool,
+    reader: Option<task::Waker>,
+    writer: Option<task::Waker>,
+}
+
+/// Creates two connected streams that use `pipe` as their storage.
+///
+/// Any data left in the pipe by streams that were previously created from it is discarded.
+pub fn duplex<const N: usize>(pipe: &mut Pipe<N>) -> (DuplexStream<'_, N>, DuplexStream<'_, N>) {
+    *pipe = Pipe::new();
+    let pipe = &*pipe;
+    let first = DuplexStream { pipe, side: 0 };
+    let second = DuplexStream { pipe, side: 1 };
+    (first, second)
+}
+
+impl<const N: usize> Pipe<N> {
+    /// Creates new storage for a pair of connected streams.
+    pub fn new() -> Self {
+        let channels = [
+            cell::RefCell::new(Channel::new()),
+            cell::RefCell::new(Channel::new()),
+        ];
+        Pipe { channels }
+    }
+}
+
+impl<const N: usize> Default for Pipe<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> DuplexStream<'_, N> {
+    fn incoming(&self) -> &cell::RefCell<Channel<N>> {
+        &self.pipe.channels[self.side]
+    }
+
+    fn outgoing(&self) -> &cell::RefCell<Channel<N>> {
+        &self.pipe.channels[1 - self.side]
+    }
+}
+
+impl<const N: usize> Channel<N> {
+    fn new() -> Self {
+        let buffer = [0; N];
+        let start = 0;
+        let len = 0;
+        let closed = false;
+        let abandoned = false;
+        let reader = None;
+        let writer = None;
+        Channel {
+            buffer,
+            start,
+            len,
+            closed,
+            abandoned,
+            reader,
+            writer,
+        }
+    }
+
+    fn read(&mut self, buffer: &mut [u8]) -> usize {
+        let n = cmp::min(buffer.len(), self.len);
+        for (i, byte) in buffer[..n].iter_mut().enumerate() {
+            *byte = self.buffer[(self.start + i) % N];
+        }
+        self.start = (self.start + n) % N;
+        self.len -= n;
+        n
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> usize {
+        let n = cmp::min(bytes.len(), N - self.len);
+        for (i, &byte) in bytes[..n].iter().enumerate() {
+            self.buffer[(self.start + self.len + i) % N] = byte;
+        }
+        self.len += n;
+        n
+    }
+}
+
+impl<const N: usize> super::Read for DuplexStream<'_, N> {
+    type Error = super::Error;
+
+    fn poll_read(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let mut channel = self.incoming().borrow_mut();
+        if buffer.is_empty() || channel.closed && channel.len == 0 {
+            return task::Poll::Ready(Ok(0));
+        }
+        if channel.len == 0 {
+            channel.reader = Some(cx.waker().clone());
+            return task::Poll::Pending;
+        }
+
+        let n = channel.read(buffer);
+        let writer = channel.writer.take();
+        drop(channel);
+        if let Some(writer) = writer {
+            writer.wake();
+        }
+        task::Poll::Ready(Ok(n))
+    }
+}
+
+impl<const N: usize> super::Write for DuplexStream<'_, N> {
+    type Error = super::Error;
+
+    fn poll_write(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let mut channel = self.outgoing().borrow_mut();
+        if bytes.is_empty() || channel.closed || channel.abandoned {
+            return task::Poll::Ready(Ok(0));
+        }
+        if channel.len == N {
+            channel.writer = Some(cx.waker().clone());
+            return task::Poll::Pending;
+        }
+
+        let n = channel.write(bytes);
+        let reader = channel.reader.take();
+        drop(channel);
+        if let Some(reader) = reader {
+            reader.wake();
+        }
+        task::Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let mut channel = self.outgoing().borrow_mut();
+        channel.closed = true;
+        let reader = channel.reader.take();
+        drop(channel);
+        if let Some(reader) = reader {
+            reader.wake();
+        }
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl<const N: usize> Drop for DuplexStream<'_, N> {
+    fn drop(&mut self) {
+        let mut outgoing = self.outgoing().borrow_mut();
+        outgoing.closed = true;
+        let reader = outgoing.reader.take();
+        drop(outgoing);
+
+        let mut incoming = self.incoming().borrow_mut();
+        incoming.abandoned = true;
+        let writer = incoming.writer.take();
+        drop(incoming);
+
+        if let Some(reader) = reader {
+            reader.wake();
+        }
+        if let Some(writer) = writer {
+            writer.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::io;
+    use core::pin;
+    use core::sync::atomic;
+    use core::task;
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct CountingWaker {
+        wakes: atomic::AtomicUsize,
+    }
+
+    impl futures::task::ArcWake for CountingWaker {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.wakes.fetch_add(1, atomic::Ordering::SeqCst);
+        }
+    }
+
+    impl CountingWaker {
+        fn wakes(&self) -> usize {
+            self.wakes.load(atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn read_wakes_a_writer_of_a_full_pipe() {
+        let mut pipe = io::Pipe::<4>::new();
+        let (mut first, mut second) = io::duplex(&mut pipe);
+        let waker = Arc::new(CountingWaker::default());
+        let task_waker = futures::task::waker(waker.clone());
+        let mut cx = task::Context::from_waker(&task_waker);
+
+        let poll = io::Write::poll_write(pin::Pin::new(&mut first), &mut cx, b"abcdef");
+        assert_eq!(poll, task::Poll::Ready(Ok(4)));
+        let poll = io::Write::poll_write(pin::Pin::new(&mut first), &mut cx, b"ef");
+        assert_eq!(poll, task::Poll::Pending);
+        assert_eq!(waker.wakes(), 0);
+
+        let mut buffer = [0; 2];
+        let poll = io::Read::poll_read(pin::Pin::new(&mut second), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Ready(Ok(2)));
+        assert_eq!(&buffer, b"ab");
+        assert_eq!(waker.wakes(), 1);
+
+        let poll = io::Write::poll_write(pin::Pin::new(&mut first), &mut cx, b"ef");
+        assert_eq!(poll, task::Poll::Ready(Ok(2)));
+
+        let mut buffer = [0; 8];
+        let poll = io::Read::poll_read(pin::Pin::new(&mut second), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Ready(Ok(4)));
+        assert_eq!(&buffer[..4], b"cdef");
+    }
+
+    #[test]
+    fn peer_drains_the_pipe_after_close() {
+        let mut pipe = io::Pipe::<8>::new();
+        let (mut first, mut second) = io::duplex(&mut pipe);
+        let waker = Arc::new(CountingWaker::default());
+        let task_waker = futures::task::waker(waker.clone());
+        let mut cx = task::Context::from_waker(&task_waker);
+        let mut buffer = [0; 8];
+
+        let poll = io::Read::poll_read(pin::Pin::new(&mut second), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Pending);
+        let poll = io::Write::poll_write(pin::Pin::new(&mut first), &mut cx, b"abc");
+        assert_eq!(poll, task::Poll::Ready(Ok(3)));
+        assert_eq!(waker.wakes(), 1);
+
+        let poll = io::Write::poll_close(pin::Pin::new(&mut first), &mut cx);
+        assert_eq!(poll, task::Poll::Ready(Ok(())));
+        let poll = io::Write::poll_write(pin::Pin::new(&mut first), &mut cx, b"d");
+        assert_eq!(poll, task::Poll::Ready(Ok(0)));
+
+        let poll = io::Read::poll_read(pin::Pin::new(&mut second), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Ready(Ok(3)));
+        assert_eq!(&buffer[..3], b"abc");
+        let poll = io::Read::poll_read(pin::Pin::new(&mut second), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Ready(Ok(0)));
+
+        // The other direction is still open
+        let poll = io::Write::poll_write(pin::Pin::new(&mut second), &mut cx, b"xyz");
+        assert_eq!(poll, task::Poll::Ready(Ok(3)));
+        let poll = io::Read::poll_read(pin::Pin::new(&mut first), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Ready(Ok(3)));
+        assert_eq!(&buffer[..3], b"xyz");
+    }
+
+    #[test]
+    fn drop_wakes_a_pending_writer_of_the_peer() {
+        let mut pipe = io::Pipe::<2>::new();
+        let (mut first, second) = io::duplex(&mut pipe);
+        let waker = Arc::new(CountingWaker::default());
+        let task_waker = futures::task::waker(waker.clone());
+        let mut cx = task::Context::from_waker(&task_waker);
+
+        let poll = io::Write::poll_write(pin::Pin::new(&mut first), &mut cx, b"ab");
+        assert_eq!(poll, task::Poll::Ready(Ok(2)));
+        let poll = io::Write::poll_write(pin::Pin::new(&mut first), &mut cx, b"c");
+        assert_eq!(poll, task::Poll::Pending);
+
+        drop(second);
+        assert_eq!(waker.wakes(), 1);
+        let poll = io::Write::poll_write(pin::Pin::new(&mut first), &mut cx, b"c");
+        assert_eq!(poll, task::Poll::Ready(Ok(0)));
+
+        let mut buffer = [0; 2];
+        let poll = io::Read::poll_read(pin::Pin::new(&mut first), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Ready(Ok(0)));
+    }
+}
@@ -0,0 +1,128 @@
+use core::convert;
+use core::fmt;
+
+/// The error type of the in-memory readers and writers in this module.
+///
+/// These never fail on their own, so this error is only ever produced by helpers that need a
+/// complete transfer, like [`read_exact`](super::ReadExt::read_exact) and
+/// [`write_all`](super::WriteExt::write_all), or by wrappers that give up after a timeout.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Error {
+    /// The reader reached its end before the requested amount of data was read.
+    UnexpectedEof,
+    /// The writer could not accept any more data.
+    WriteZero,
+    /// The operation did not complete in time.
+    TimedOut,
+}
+
+/// A general category of IO errors, returned by [`IoError::kind`](super::IoError::kind).
+///
+/// This is also the fixed error type of the object-safe traits like [`DynRead`](super::DynRead),
+/// which is why errors from other parts of the platform, like pins and timers, are converted into
+/// it as well.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorKind {
+    /// The reader reached its end before the requested amount of data was read.
+    UnexpectedEof,
+    /// The writer could not accept any more data.
+    WriteZero,
+    /// The operation did not complete in time.
+    TimedOut,
+    /// The other side did not acknowledge an address or some data, for example on an I²C bus.
+    Nack,
+    /// Data was received faster than it could be processed, so some of it was lost.
+    Overrun,
+    /// Data was received with an invalid framing, for example a missing stop bit.
+    Framing,
+    /// Data was received with an invalid parity bit.
+    Parity,
+    /// Another controller took over a shared bus in the middle of a transfer.
+    ArbitrationLost,
+    /// The peripheral is currently being used by something else.
+    Busy,
+    /// Any other error.
+    Other,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::UnexpectedEof => f.write_str("unexpected end of data"),
+            Error::WriteZero => f.write_str("no more data could be written"),
+            Error::TimedOut => f.write_str("the operation timed out"),
+        }
+    }
+}
+
+impl super::IoError for Error {
+    fn kind(&self) -> ErrorKind {
+        match *self {
+            Error::UnexpectedEof => ErrorKind::UnexpectedEof,
+            Error::WriteZero => ErrorKind::WriteZero,
+            Error::TimedOut => ErrorKind::TimedOut,
+        }
+    }
+
+    fn timed_out() -> Self {
+        Error::TimedOut
+    }
+}
+
+impl super::ReadError for Error {
+    fn eof() -> Self {
+        Error::UnexpectedEof
+    }
+}
+
+impl super::WriteError for Error {
+    fn write_zero() -> Self {
+        Error::WriteZero
+    }
+}
+
+impl super::IoError for ErrorKind {
+    fn kind(&self) -> ErrorKind {
+        *self
+    }
+
+    fn timed_out() -> Self {
+        ErrorKind::TimedOut
+    }
+}
+
+impl super::ReadError for ErrorKind {
+    fn eof() -> Self {
+        ErrorKind::UnexpectedEof
+    }
+}
+
+impl super::WriteError for ErrorKind {
+    fn write_zero() -> Self {
+        ErrorKind::WriteZero
+    }
+}
+
+impl From<convert::Infallible> for ErrorKind {
+    fn from(never: convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match *self {
+            ErrorKind::UnexpectedEof => "unexpected end of data",
+            ErrorKind::WriteZero => "no more data could be written",
+            ErrorKind::TimedOut => "timed out",
+            ErrorKind::Nack => "not acknowledged",
+            ErrorKind::Overrun => "overrun",
+            ErrorKind::Framing => "framing error",
+            ErrorKind::Parity => "parity error",
+            ErrorKind::ArbitrationLost => "arbitration lost",
+            ErrorKind::Busy => "busy",
+            ErrorKind::Other => "other error",
+        };
+        f.write_str(description)
+    }
+}
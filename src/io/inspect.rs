@@ -0,0 +1,129 @@
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// A reader or writer that calls a function with all bytes that pass through it.
+///
+/// When wrapping something that is both a reader and a writer, the function observes the bytes
+/// flowing in both directions.
+pub struct Inspect<A, F> {
+    inner: A,
+    f: F,
+}
+
+pub fn inspect<A, F>(inner: A, f: F) -> Inspect<A, F>
+where
+    F: FnMut(&[u8]),
+{
+    Inspect { inner, f }
+}
+
+impl<A, F> Inspect<A, F> {
+    /// Returns a reference to the underlying reader or writer.
+    pub fn get_ref(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader or writer.
+    pub fn get_mut(&mut self) -> &mut A {
+        &mut self.inner
+    }
+
+    /// Consumes this adapter, returning the underlying reader or writer.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+impl<A, F> fmt::Debug for Inspect<A, F>
+where
+    A: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inspect")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<A, F> super::Read for Inspect<A, F>
+where
+    A: super::Read + Unpin,
+    F: FnMut(&[u8]) + Unpin,
+{
+    type Error = A::Error;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        let n = futures::ready!(pin::Pin::new(&mut this.inner).poll_read(cx, buffer))?;
+        (this.f)(&buffer[..n]);
+        task::Poll::Ready(Ok(n))
+    }
+}
+
+impl<A, F> super::Write for Inspect<A, F>
+where
+    A: super::Write + Unpin,
+    F: FnMut(&[u8]) + Unpin,
+{
+    type Error = A::Error;
+
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        let n = futures::ready!(pin::Pin::new(&mut this.inner).poll_write(cx, bytes))?;
+        (this.f)(&bytes[..n]);
+        task::Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn sees_bytes_in_both_directions() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 8];
+            let mut seen = [0; 8];
+            let mut len = 0;
+
+            {
+                let cursor = io::Cursor::new(&mut buffer[..]);
+                let mut cursor = cursor.inspect_written(|bytes: &[u8]| {
+                    seen[len..len + bytes.len()].copy_from_slice(bytes);
+                    len += bytes.len();
+                });
+
+                cursor.write_all(b"ping").await.unwrap();
+                cursor.get_mut().set_position(0);
+                let mut bytes = [0; 4];
+                cursor.read_exact(&mut bytes).await.unwrap();
+            }
+
+            assert_eq!(&seen[..len], b"pingping");
+        });
+    }
+}
@@ -0,0 +1,85 @@
+use core::cmp;
+use core::pin;
+use core::task;
+
+/// A writer that writes at most a limited number of bytes to an underlying writer.
+///
+/// Once the limit has been reached, every write writes zero bytes, which makes
+/// [`write_all`](super::WriteExt::write_all) fail with a
+/// [`WriteError::write_zero`](super::WriteError) error.
+#[derive(Debug)]
+pub struct Limit<W> {
+    writer: W,
+    limit: u64,
+}
+
+pub fn limit<W>(writer: W, limit: u64) -> Limit<W>
+where
+    W: super::Write,
+{
+    Limit { writer, limit }
+}
+
+impl<W> Limit<W> {
+    /// The number of bytes that can still be written before the limit is reached.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Changes the number of bytes that can still be written before the limit is reached.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Consumes this adapter, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> super::Write for Limit<W>
+where
+    W: super::Write + Unpin,
+{
+    type Error = W::Error;
+
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        if this.limit == 0 {
+            return task::Poll::Ready(Ok(0));
+        }
+
+        let max = cmp::min(bytes.len() as u64, this.limit) as usize;
+        let n = futures::ready!(pin::Pin::new(&mut this.writer).poll_write(cx, &bytes[..max]))?;
+        this.limit -= n as u64;
+        task::Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.writer).poll_flush(cx)
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut self.writer).poll_close(cx)
+    }
+}
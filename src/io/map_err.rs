@@ -0,0 +1,186 @@
+use core::fmt;
+use core::pin;
+use core::task;
+
+/// A reader or writer that converts the errors of an underlying reader or writer.
+///
+/// This makes it possible to use readers and writers of different peripherals, which usually have
+/// different error types, together in code that expects a single error type.
+pub struct MapErr<A, F> {
+    inner: A,
+    f: F,
+}
+
+pub fn map_err<A, F>(inner: A, f: F) -> MapErr<A, F> {
+    MapErr { inner, f }
+}
+
+impl<A, F> MapErr<A, F> {
+    /// Returns a reference to the underlying reader or writer.
+    pub fn get_ref(&self) -> &A {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader or writer.
+    pub fn get_mut(&mut self) -> &mut A {
+        &mut self.inner
+    }
+
+    /// Consumes this adapter, returning the underlying reader or writer.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+impl<A, F> fmt::Debug for MapErr<A, F>
+where
+    A: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapErr")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<A, F, E> super::Read for MapErr<A, F>
+where
+    A: super::Read + Unpin,
+    F: FnMut(A::Error) -> E + Unpin,
+    E: super::ReadError,
+{
+    type Error = E;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        pin::Pin::new(&mut this.inner)
+            .poll_read(cx, buffer)
+            .map_err(&mut this.f)
+    }
+
+    fn poll_read_vectored(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffers: &mut [super::IoSliceMut<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        pin::Pin::new(&mut this.inner)
+            .poll_read_vectored(cx, buffers)
+            .map_err(&mut this.f)
+    }
+}
+
+impl<A, F, E> super::BufRead for MapErr<A, F>
+where
+    A: super::BufRead + Unpin,
+    F: FnMut(A::Error) -> E + Unpin,
+    E: super::ReadError,
+{
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<&[u8], Self::Error>> {
+        let this = self.get_mut();
+        pin::Pin::new(&mut this.inner)
+            .poll_fill_buf(cx)
+            .map_err(&mut this.f)
+    }
+
+    fn consume(mut self: pin::Pin<&mut Self>, amount: usize) {
+        pin::Pin::new(&mut self.inner).consume(amount)
+    }
+}
+
+impl<A, F, E> super::Write for MapErr<A, F>
+where
+    A: super::Write + Unpin,
+    F: FnMut(A::Error) -> E + Unpin,
+    E: super::WriteError,
+{
+    type Error = E;
+
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        pin::Pin::new(&mut this.inner)
+            .poll_write(cx, bytes)
+            .map_err(&mut this.f)
+    }
+
+    fn poll_write_vectored(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffers: &[super::IoSlice<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        pin::Pin::new(&mut this.inner)
+            .poll_write_vectored(cx, buffers)
+            .map_err(&mut this.f)
+    }
+
+    fn poll_flush(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = &mut *self;
+        pin::Pin::new(&mut this.inner)
+            .poll_flush(cx)
+            .map_err(&mut this.f)
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        let this = &mut *self;
+        pin::Pin::new(&mut this.inner)
+            .poll_close(cx)
+            .map_err(&mut this.f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::io::IoError;
+    use crate::prelude::*;
+
+    #[test]
+    fn converts_errors_of_readers_and_writers() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 4];
+            let cursor = io::Cursor::new(&mut buffer[..]);
+            let mut cursor = cursor.map_read_err(|e: io::Error| e.kind());
+
+            let result = cursor.write_all(b"hello").await;
+            assert_eq!(result, Err(io::ErrorKind::WriteZero));
+
+            cursor.get_mut().set_position(0);
+            let mut bytes = [0; 5];
+            let result = cursor.read_exact(&mut bytes).await;
+            assert_eq!(result, Err(io::ErrorKind::UnexpectedEof));
+        });
+    }
+
+    #[test]
+    fn converts_errors_of_writers_and_readers() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 4];
+            let cursor = io::Cursor::new(&mut buffer[..]);
+            let mut cursor = cursor.map_write_err(|e: io::Error| e.kind());
+
+            cursor.write_all(b"hell").await.unwrap();
+            cursor.get_mut().set_position(0);
+            let mut bytes = [0; 5];
+            let result = cursor.read_exact(&mut bytes).await;
+            assert_eq!(result, Err(io::ErrorKind::UnexpectedEof));
+        });
+    }
+}
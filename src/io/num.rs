@@ -0,0 +1,62 @@
+//! Numbers with a fixed-size binary representation, as read by e.g.
+//! [`ReadExt::read_u16_le`](super::ReadExt::read_u16_le) and written by e.g.
+//! [`WriteExt::write_u16_le`](super::WriteExt::write_u16_le).
+use core::fmt;
+
+/// The order of the bytes in the binary representation of a number.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Endian {
+    /// The least significant byte comes first.
+    Little,
+    /// The most significant byte comes first.
+    Big,
+}
+
+/// A number that can be converted to and from a fixed-size array of bytes.
+pub trait Number: Copy + Unpin + fmt::Debug {
+    /// The binary representation of the number.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Copy + Default + Unpin + fmt::Debug;
+
+    /// Converts bytes in the specified order into a number.
+    fn from_bytes(bytes: Self::Bytes, endian: Endian) -> Self;
+
+    /// Converts this number into bytes in the specified order.
+    fn to_bytes(self, endian: Endian) -> Self::Bytes;
+}
+
+macro_rules! number {
+    ($($ty:ty: $size:expr,)*) => {
+        $(
+            impl Number for $ty {
+                type Bytes = [u8; $size];
+
+                fn from_bytes(bytes: Self::Bytes, endian: Endian) -> Self {
+                    match endian {
+                        Endian::Little => <$ty>::from_le_bytes(bytes),
+                        Endian::Big => <$ty>::from_be_bytes(bytes),
+                    }
+                }
+
+                fn to_bytes(self, endian: Endian) -> Self::Bytes {
+                    match endian {
+                        Endian::Little => self.to_le_bytes(),
+                        Endian::Big => self.to_be_bytes(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+number! {
+    u8: 1,
+    i8: 1,
+    u16: 2,
+    i16: 2,
+    u32: 4,
+    i32: 4,
+    u64: 8,
+    i64: 8,
+    f32: 4,
+    f64: 8,
+}
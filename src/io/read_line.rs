@@ -0,0 +1,51 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadLine<'a, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    inner: super::read_until::ReadUntil<'a, A>,
+}
+
+pub fn read_line<'a, A>(reader: &'a mut A, buffer: &'a mut [u8]) -> ReadLine<'a, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    let inner = super::read_until::read_until(reader, b'\n', buffer);
+    ReadLine { inner }
+}
+
+impl<A> future::Future for ReadLine<'_, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    type Output = Result<usize, A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        pin::Pin::new(&mut self.inner).poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn reads_one_line_at_a_time() {
+        futures::executor::block_on(async {
+            let mut reader = io::BufReader::<_, 4>::new(io::Cursor::new(b"hello\nworld"));
+            let mut buffer = [0; 16];
+
+            let n = reader.read_line(&mut buffer).await.unwrap();
+            assert_eq!(&buffer[..n], b"hello\n");
+
+            let n = reader.read_line(&mut buffer).await.unwrap();
+            assert_eq!(&buffer[..n], b"world");
+        });
+    }
+}
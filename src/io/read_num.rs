@@ -0,0 +1,58 @@
+use core::future;
+use core::marker;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadNum<'a, A: ?Sized, T>
+where
+    T: super::num::Number,
+{
+    reader: &'a mut A,
+    endian: super::num::Endian,
+    bytes: T::Bytes,
+    position: usize,
+    phantom: marker::PhantomData<fn() -> T>,
+}
+
+pub fn read_num<A, T>(reader: &mut A, endian: super::num::Endian) -> ReadNum<'_, A, T>
+where
+    A: super::Read + Unpin + ?Sized,
+    T: super::num::Number,
+{
+    let bytes = T::Bytes::default();
+    let position = 0;
+    let phantom = marker::PhantomData;
+    ReadNum {
+        reader,
+        endian,
+        bytes,
+        position,
+        phantom,
+    }
+}
+
+impl<A, T> future::Future for ReadNum<'_, A, T>
+where
+    A: super::Read + Unpin + ?Sized,
+    T: super::num::Number,
+{
+    type Output = Result<T, A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        use super::ReadError;
+
+        let this = &mut *self;
+        while this.position < this.bytes.as_ref().len() {
+            let n = futures::ready!(pin::Pin::new(&mut *this.reader)
+                .poll_read(cx, &mut this.bytes.as_mut()[this.position..]))?;
+            if n == 0 {
+                return task::Poll::Ready(Err(A::Error::eof()));
+            }
+            this.position += n;
+        }
+
+        task::Poll::Ready(Ok(T::from_bytes(this.bytes, this.endian)))
+    }
+}
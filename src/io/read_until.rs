@@ -0,0 +1,113 @@
+use core::cmp;
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadUntil<'a, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    reader: &'a mut A,
+    delimiter: u8,
+    buffer: &'a mut [u8],
+    position: usize,
+}
+
+pub fn read_until<'a, A>(reader: &'a mut A, delimiter: u8, buffer: &'a mut [u8]) -> ReadUntil<'a, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    let position = 0;
+    ReadUntil {
+        reader,
+        delimiter,
+        buffer,
+        position,
+    }
+}
+
+impl<A> future::Future for ReadUntil<'_, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    type Output = Result<usize, A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = &mut *self;
+        let delimiter = this.delimiter;
+
+        while this.position < this.buffer.len() {
+            let available = futures::ready!(pin::Pin::new(&mut *this.reader).poll_fill_buf(cx))?;
+            if available.is_empty() {
+                break;
+            }
+
+            let space = cmp::min(available.len(), this.buffer.len() - this.position);
+            let (n, found) = match available[..space].iter().position(|&b| b == delimiter) {
+                Some(i) => (i + 1, true),
+                None => (space, false),
+            };
+            this.buffer[this.position..this.position + n].copy_from_slice(&available[..n]);
+            pin::Pin::new(&mut *this.reader).consume(n);
+            this.position += n;
+
+            if found {
+                break;
+            }
+        }
+
+        task::Poll::Ready(Ok(this.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn finds_a_delimiter_in_a_later_chunk() {
+        futures::executor::block_on(async {
+            let mut reader = io::BufReader::<_, 4>::new(io::Cursor::new(b"abcdef;gh"));
+            let mut buffer = [0; 16];
+
+            let n = reader.read_until(b';', &mut buffer).await.unwrap();
+            assert_eq!(&buffer[..n], b"abcdef;");
+            assert_eq!(reader.buffer(), b"g");
+
+            let n = reader.read_until(b';', &mut buffer).await.unwrap();
+            assert_eq!(&buffer[..n], b"gh");
+        });
+    }
+
+    #[test]
+    fn stops_when_the_buffer_is_full() {
+        futures::executor::block_on(async {
+            let mut reader = io::BufReader::<_, 4>::new(io::Cursor::new(b"abcdef;"));
+            let mut buffer = [0; 5];
+
+            let n = reader.read_until(b';', &mut buffer).await.unwrap();
+            assert_eq!(&buffer[..n], b"abcde");
+
+            // The rest of the record is left for the next read
+            let n = reader.read_until(b';', &mut buffer).await.unwrap();
+            assert_eq!(&buffer[..n], b"f;");
+        });
+    }
+
+    #[test]
+    fn stops_at_the_end_of_the_reader() {
+        futures::executor::block_on(async {
+            let mut reader = io::Cursor::new(b"abc");
+            let mut buffer = [0; 8];
+
+            let n = reader.read_until(b';', &mut buffer).await.unwrap();
+            assert_eq!(&buffer[..n], b"abc");
+
+            let n = reader.read_until(b';', &mut buffer).await.unwrap();
+            assert_eq!(n, 0);
+        });
+    }
+}
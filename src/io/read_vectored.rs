@@ -0,0 +1,32 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ReadVectored<'a, 'b, A: ?Sized> {
+    reader: &'a mut A,
+    buffers: &'a mut [super::IoSliceMut<'b>],
+}
+
+pub fn read_vectored<'a, 'b, A>(
+    reader: &'a mut A,
+    buffers: &'a mut [super::IoSliceMut<'b>],
+) -> ReadVectored<'a, 'b, A>
+where
+    A: super::Read + Unpin + ?Sized,
+{
+    ReadVectored { reader, buffers }
+}
+
+impl<A> future::Future for ReadVectored<'_, '_, A>
+where
+    A: super::Read + Unpin + ?Sized,
+{
+    type Output = Result<usize, A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = &mut *self;
+        pin::Pin::new(&mut *this.reader).poll_read_vectored(cx, this.buffers)
+    }
+}
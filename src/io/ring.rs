@@ -0,0 +1,394 @@
+//! A single-producer/single-consumer byte ring buffer for handing data between an interrupt
+//! handler and a task.
+//!
+//! A [`Ring`] is split into a [`Producer`] and a [`Consumer`].  Both halves have non-blocking
+//! methods that never wait and can be called from interrupt context, like [`Producer::push`] in a
+//! UART receive interrupt.  The halves also implement [`io::Write`](super::Write) and
+//! [`io::Read`](super::Read) respectively, so a task can wait for data (or space) to become
+//! available, and pushing or popping data wakes the task waiting on the other half.
+//!
+//! Since a ring is usually shared with an interrupt handler, it needs to live in a `'static`
+//! place, for example one created with `cortex_m::singleton!`:
+//!
+//! ```ignore
+//! let ring: &'static mut Ring<64> = cortex_m::singleton!(: Ring<64> = Ring::new()).unwrap();
+//! let (producer, consumer) = ring.split();
+//! ```
+use core::cmp;
+use core::pin;
+use core::sync::atomic;
+use core::task;
+use futures::task::AtomicWaker;
+
+/// A byte ring buffer with room for `N` bytes.
+#[derive(Debug)]
+pub struct Ring<const N: usize> {
+    buffer: [atomic::AtomicU8; N],
+    // Both indices count up to `2 * N`, so that a full ring can be told apart from an empty one.
+    head: atomic::AtomicUsize,
+    tail: atomic::AtomicUsize,
+    closed: atomic::AtomicBool,
+    reader: AtomicWaker,
+    writer: AtomicWaker,
+}
+
+/// The half of a [`Ring`] that data is pushed into.
+#[derive(Debug)]
+pub struct Producer<'a, const N: usize> {
+    ring: &'a Ring<N>,
+}
+
+/// The half of a [`Ring`] that data is popped from.
+#[derive(Debug)]
+pub struct Consumer<'a, const N: usize> {
+    ring: &'a Ring<N>,
+}
+
+impl<const N: usize> Ring<N> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY: atomic::AtomicU8 = atomic::AtomicU8::new(0);
+    // The indices are taken modulo `N`, so an empty ring would divide by zero.
+    const NOT_EMPTY: () = assert!(N > 0, "a ring must have room for at least one byte");
+
+    /// Creates a new empty ring.
+    ///
+    /// Fails to compile if `N` is zero.
+    pub const fn new() -> Self {
+        let () = Self::NOT_EMPTY;
+        Ring {
+            buffer: [Self::EMPTY; N],
+            head: atomic::AtomicUsize::new(0),
+            tail: atomic::AtomicUsize::new(0),
+            closed: atomic::AtomicBool::new(false),
+            reader: AtomicWaker::new(),
+            writer: AtomicWaker::new(),
+        }
+    }
+
+    /// Splits this ring into its producer and consumer halves.
+    ///
+    /// Any data left in the ring by halves that were previously split from it is discarded.
+    pub fn split(&mut self) -> (Producer<'_, N>, Consumer<'_, N>) {
+        *self.head.get_mut() = 0;
+        *self.tail.get_mut() = 0;
+        *self.closed.get_mut() = false;
+        let ring = &*self;
+        (Producer { ring }, Consumer { ring })
+    }
+
+    /// The total number of bytes that fit in the ring.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// The number of bytes that have been pushed but not popped yet.
+    pub fn len(&self) -> usize {
+        let head = self.head.load(atomic::Ordering::Acquire);
+        let tail = self.tail.load(atomic::Ordering::Acquire);
+        distance(tail, head, N)
+    }
+
+    /// Whether there are no bytes to pop.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const N: usize> Default for Ring<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Producer<'_, N> {
+    /// Pushes a single byte, returning it back if the ring is full.
+    pub fn push(&mut self, byte: u8) -> Result<(), u8> {
+        if self.push_slice(&[byte]) == 1 {
+            Ok(())
+        } else {
+            Err(byte)
+        }
+    }
+
+    /// Pushes as many bytes as fit in the ring, returning the number of bytes pushed.
+    pub fn push_slice(&mut self, bytes: &[u8]) -> usize {
+        let ring = self.ring;
+        let head = ring.head.load(atomic::Ordering::Relaxed);
+        let tail = ring.tail.load(atomic::Ordering::Acquire);
+        let n = cmp::min(bytes.len(), N - distance(tail, head, N));
+
+        for (i, &byte) in bytes[..n].iter().enumerate() {
+            ring.buffer[(head + i) % N].store(byte, atomic::Ordering::Relaxed);
+        }
+        ring.head
+            .store((head + n) % (2 * N), atomic::Ordering::Release);
+
+        if n > 0 {
+            ring.reader.wake();
+        }
+        n
+    }
+
+    /// Whether there is no room for any more bytes.
+    pub fn is_full(&self) -> bool {
+        self.ring.len() == N
+    }
+
+    /// Marks the ring as closed, so that the consumer reaches its end once it has popped all the
+    /// remaining data.
+    ///
+    /// This also happens when the producer is dropped.
+    pub fn close(&mut self) {
+        self.ring.closed.store(true, atomic::Ordering::Release);
+        self.ring.reader.wake();
+    }
+}
+
+impl<const N: usize> Drop for Producer<'_, N> {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl<const N: usize> Consumer<'_, N> {
+    /// Pops a single byte, if there is one.
+    pub fn pop(&mut self) -> Option<u8> {
+        let mut byte = [0];
+        if self.pop_slice(&mut byte) == 1 {
+            Some(byte[0])
+        } else {
+            None
+        }
+    }
+
+    /// Pops as many bytes as are available and fit in `buffer`, returning the number of bytes
+    /// popped.
+    pub fn pop_slice(&mut self, buffer: &mut [u8]) -> usize {
+        let ring = self.ring;
+        let tail = ring.tail.load(atomic::Ordering::Relaxed);
+        let head = ring.head.load(atomic::Ordering::Acquire);
+        let n = cmp::min(buffer.len(), distance(tail, head, N));
+
+        for (i, byte) in buffer[..n].iter_mut().enumerate() {
+            *byte = ring.buffer[(tail + i) % N].load(atomic::Ordering::Relaxed);
+        }
+        ring.tail
+            .store((tail + n) % (2 * N), atomic::Ordering::Release);
+
+        if n > 0 {
+            ring.writer.wake();
+        }
+        n
+    }
+
+    /// The number of bytes that are available to pop.
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Whether there are no bytes to pop.
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    /// Whether the producer has been closed (or dropped).
+    ///
+    /// There might still be data left to pop.
+    pub fn is_closed(&self) -> bool {
+        self.ring.closed.load(atomic::Ordering::Acquire)
+    }
+}
+
+impl<const N: usize> super::Read for Consumer<'_, N> {
+    type Error = super::Error;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        if buffer.is_empty() {
+            return task::Poll::Ready(Ok(0));
+        }
+
+        // Check whether the producer closed the ring before popping, so that data pushed right
+        // before closing isn't missed.
+        let closed = self.is_closed();
+        let n = self.pop_slice(buffer);
+        if n > 0 || closed {
+            return task::Poll::Ready(Ok(n));
+        }
+
+        // Register before checking again, so that a push in between is never missed.
+        self.ring.reader.register(cx.waker());
+        let closed = self.is_closed();
+        let n = self.pop_slice(buffer);
+        if n > 0 || closed {
+            task::Poll::Ready(Ok(n))
+        } else {
+            task::Poll::Pending
+        }
+    }
+}
+
+impl<const N: usize> super::Write for Producer<'_, N> {
+    type Error = super::Error;
+
+    fn poll_write(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        if bytes.is_empty() {
+            return task::Poll::Ready(Ok(0));
+        }
+
+        let n = self.push_slice(bytes);
+        if n > 0 {
+            return task::Poll::Ready(Ok(n));
+        }
+
+        // Register before checking again, so that a pop in between is never missed.
+        self.ring.writer.register(cx.waker());
+        let n = self.push_slice(bytes);
+        if n > 0 {
+            task::Poll::Ready(Ok(n))
+        } else {
+            task::Poll::Pending
+        }
+    }
+
+    fn poll_flush(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        mut self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        self.close();
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+/// The number of bytes between `tail` and `head`, where both count up to `2 * n`.
+fn distance(tail: usize, head: usize, n: usize) -> usize {
+    (head + 2 * n - tail) % (2 * n)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::Ring;
+    use crate::io;
+    use core::pin;
+    use core::sync::atomic;
+    use core::task;
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct CountingWaker {
+        wakes: atomic::AtomicUsize,
+    }
+
+    impl futures::task::ArcWake for CountingWaker {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.wakes.fetch_add(1, atomic::Ordering::SeqCst);
+        }
+    }
+
+    impl CountingWaker {
+        fn wakes(&self) -> usize {
+            self.wakes.load(atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn wraps_around() {
+        let mut ring = Ring::<4>::new();
+        let (mut producer, mut consumer) = ring.split();
+
+        // Every round starts at a different offset, so that all positions of the buffer are
+        // crossed several times.
+        for round in 0..10u8 {
+            assert_eq!(producer.push_slice(&[round, round + 1, round + 2]), 3);
+            let mut buffer = [0; 3];
+            assert_eq!(consumer.pop_slice(&mut buffer), 3);
+            assert_eq!(buffer, [round, round + 1, round + 2]);
+        }
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn tells_full_from_empty() {
+        let mut ring = Ring::<3>::new();
+        let (mut producer, mut consumer) = ring.split();
+        assert!(consumer.is_empty());
+        assert_eq!(consumer.pop(), None);
+
+        assert_eq!(producer.push_slice(b"abcd"), 3);
+        assert!(producer.is_full());
+        assert_eq!(consumer.len(), 3);
+        assert_eq!(producer.push(b'e'), Err(b'e'));
+
+        assert_eq!(consumer.pop(), Some(b'a'));
+        assert!(!producer.is_full());
+        assert_eq!(producer.push(b'e'), Ok(()));
+        assert!(producer.is_full());
+
+        let mut buffer = [0; 8];
+        assert_eq!(consumer.pop_slice(&mut buffer), 3);
+        assert_eq!(&buffer[..3], b"bce");
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn producer_wakes_consumer() {
+        let mut ring = Ring::<4>::new();
+        let (mut producer, mut consumer) = ring.split();
+        let waker = Arc::new(CountingWaker::default());
+        let task_waker = futures::task::waker(waker.clone());
+        let mut cx = task::Context::from_waker(&task_waker);
+
+        let mut buffer = [0; 4];
+        let poll = io::Read::poll_read(pin::Pin::new(&mut consumer), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Pending);
+        assert_eq!(waker.wakes(), 0);
+
+        producer.push(b'x').unwrap();
+        assert_eq!(waker.wakes(), 1);
+        let poll = io::Read::poll_read(pin::Pin::new(&mut consumer), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Ready(Ok(1)));
+        assert_eq!(buffer[0], b'x');
+
+        let poll = io::Read::poll_read(pin::Pin::new(&mut consumer), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Pending);
+        drop(producer);
+        assert_eq!(waker.wakes(), 2);
+        let poll = io::Read::poll_read(pin::Pin::new(&mut consumer), &mut cx, &mut buffer);
+        assert_eq!(poll, task::Poll::Ready(Ok(0)));
+    }
+
+    #[test]
+    fn consumer_wakes_producer() {
+        let mut ring = Ring::<2>::new();
+        let (mut producer, mut consumer) = ring.split();
+        let waker = Arc::new(CountingWaker::default());
+        let task_waker = futures::task::waker(waker.clone());
+        let mut cx = task::Context::from_waker(&task_waker);
+
+        let poll = io::Write::poll_write(pin::Pin::new(&mut producer), &mut cx, b"abc");
+        assert_eq!(poll, task::Poll::Ready(Ok(2)));
+        let poll = io::Write::poll_write(pin::Pin::new(&mut producer), &mut cx, b"c");
+        assert_eq!(poll, task::Poll::Pending);
+
+        assert_eq!(consumer.pop(), Some(b'a'));
+        assert_eq!(waker.wakes(), 1);
+        let poll = io::Write::poll_write(pin::Pin::new(&mut producer), &mut cx, b"c");
+        assert_eq!(poll, task::Poll::Ready(Ok(1)));
+    }
+}
@@ -0,0 +1,77 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SkipUntil<'a, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    reader: &'a mut A,
+    delimiter: u8,
+    skipped: usize,
+}
+
+pub fn skip_until<A>(reader: &mut A, delimiter: u8) -> SkipUntil<'_, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    let skipped = 0;
+    SkipUntil {
+        reader,
+        delimiter,
+        skipped,
+    }
+}
+
+impl<A> future::Future for SkipUntil<'_, A>
+where
+    A: super::BufRead + Unpin + ?Sized,
+{
+    type Output = Result<usize, A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = &mut *self;
+        let delimiter = this.delimiter;
+
+        loop {
+            let available = futures::ready!(pin::Pin::new(&mut *this.reader).poll_fill_buf(cx))?;
+            if available.is_empty() {
+                return task::Poll::Ready(Ok(this.skipped));
+            }
+
+            let (n, found) = match available.iter().position(|&b| b == delimiter) {
+                Some(i) => (i + 1, true),
+                None => (available.len(), false),
+            };
+            pin::Pin::new(&mut *this.reader).consume(n);
+            this.skipped += n;
+
+            if found {
+                return task::Poll::Ready(Ok(this.skipped));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn consumes_exactly_through_the_delimiter() {
+        futures::executor::block_on(async {
+            let mut reader = io::BufReader::<_, 4>::new(io::Cursor::new(b"noise;data"));
+
+            assert_eq!(reader.skip_until(b';').await.unwrap(), 6);
+
+            let mut buffer = [0; 4];
+            reader.read_exact(&mut buffer).await.unwrap();
+            assert_eq!(&buffer, b"data");
+
+            assert_eq!(reader.skip_until(b';').await.unwrap(), 0);
+        });
+    }
+}
@@ -0,0 +1,112 @@
+use core::mem;
+use core::ops;
+
+/// A borrowed buffer to write from, used as one element of a scatter/gather list in
+/// [`Write::poll_write_vectored`](super::Write::poll_write_vectored).
+#[derive(Clone, Copy, Debug)]
+pub struct IoSlice<'a> {
+    bytes: &'a [u8],
+}
+
+/// A borrowed buffer to read into, used as one element of a scatter/gather list in
+/// [`Read::poll_read_vectored`](super::Read::poll_read_vectored).
+#[derive(Debug)]
+pub struct IoSliceMut<'a> {
+    bytes: &'a mut [u8],
+}
+
+impl<'a> IoSlice<'a> {
+    /// Wraps the specified bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        IoSlice { bytes }
+    }
+
+    /// Skips the first `n` bytes of this slice.
+    ///
+    /// Panics if `n` is larger than the length of the slice.
+    pub fn advance(&mut self, n: usize) {
+        self.bytes = &self.bytes[n..];
+    }
+
+    /// Skips the first `n` bytes of a list of slices, removing slices from the list once they
+    /// have been skipped entirely.
+    ///
+    /// Panics if `n` is larger than the total length of the slices.
+    pub fn advance_slices(slices: &mut &mut [IoSlice<'a>], n: usize) {
+        let mut remaining = n;
+        let mut removed = 0;
+        for slice in slices.iter() {
+            if slice.len() > remaining {
+                break;
+            }
+            remaining -= slice.len();
+            removed += 1;
+        }
+
+        *slices = &mut mem::take(slices)[removed..];
+        if slices.is_empty() {
+            assert!(remaining == 0, "advancing past the end of the slices");
+        } else {
+            slices[0].advance(remaining);
+        }
+    }
+}
+
+impl ops::Deref for IoSlice<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl<'a> IoSliceMut<'a> {
+    /// Wraps the specified buffer.
+    pub fn new(bytes: &'a mut [u8]) -> Self {
+        IoSliceMut { bytes }
+    }
+
+    /// Skips the first `n` bytes of this buffer.
+    ///
+    /// Panics if `n` is larger than the length of the buffer.
+    pub fn advance(&mut self, n: usize) {
+        self.bytes = &mut mem::take(&mut self.bytes)[n..];
+    }
+
+    /// Skips the first `n` bytes of a list of buffers, removing buffers from the list once they
+    /// have been skipped entirely.
+    ///
+    /// Panics if `n` is larger than the total length of the buffers.
+    pub fn advance_slices(slices: &mut &mut [IoSliceMut<'a>], n: usize) {
+        let mut remaining = n;
+        let mut removed = 0;
+        for slice in slices.iter() {
+            if slice.len() > remaining {
+                break;
+            }
+            remaining -= slice.len();
+            removed += 1;
+        }
+
+        *slices = &mut mem::take(slices)[removed..];
+        if slices.is_empty() {
+            assert!(remaining == 0, "advancing past the end of the buffers");
+        } else {
+            slices[0].advance(remaining);
+        }
+    }
+}
+
+impl ops::Deref for IoSliceMut<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl ops::DerefMut for IoSliceMut<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.bytes
+    }
+}
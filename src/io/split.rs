@@ -0,0 +1,162 @@
+use core::cell;
+use core::pin;
+use core::task;
+
+/// Storage for a stream that has been split into a [`ReadHalf`] and a [`WriteHalf`] by [`split`].
+///
+/// The halves borrow the storage, so to hand them to tasks spawned on an executor, the storage
+/// needs to live in a `'static` place, for example one created with `cortex_m::singleton!`:
+///
+/// ```ignore
+/// let uart: &'static mut Split<_> = cortex_m::singleton!(: Split<Uart> = Split::new(uart)).unwrap();
+/// let (rx, tx) = io::split(uart);
+/// spawner.spawn(receive_loop(rx))?;
+/// spawner.spawn(transmit_loop(tx))?;
+/// ```
+#[derive(Debug)]
+pub struct Split<T> {
+    stream: cell::RefCell<T>,
+}
+
+/// The reading half of a stream, created by [`split`].
+#[derive(Debug)]
+pub struct ReadHalf<'a, T> {
+    stream: &'a cell::RefCell<T>,
+}
+
+/// The writing half of a stream, created by [`split`].
+#[derive(Debug)]
+pub struct WriteHalf<'a, T> {
+    stream: &'a cell::RefCell<T>,
+}
+
+/// Splits a stream that is both a reader and a writer into two halves, that can be used from
+/// different tasks.
+///
+/// The stream is only borrowed for the duration of each individual poll, so the halves must be
+/// used from the same thread, which is usually the case for tasks spawned on the same executor.
+/// Polling one half while the other half is being polled, for example from a waker that polls
+/// synchronously when the stream wakes it, panics because the stream is already borrowed.
+pub fn split<T>(storage: &mut Split<T>) -> (ReadHalf<'_, T>, WriteHalf<'_, T>)
+where
+    T: super::Read + super::Write + Unpin,
+{
+    let stream = &storage.stream;
+    (ReadHalf { stream }, WriteHalf { stream })
+}
+
+impl<T> Split<T> {
+    /// Creates storage for splitting the specified stream.
+    pub const fn new(stream: T) -> Self {
+        let stream = cell::RefCell::new(stream);
+        Split { stream }
+    }
+
+    /// Consumes this storage, returning the stream.
+    pub fn into_inner(self) -> T {
+        self.stream.into_inner()
+    }
+}
+
+impl<T> super::Read for ReadHalf<'_, T>
+where
+    T: super::Read + Unpin,
+{
+    type Error = T::Error;
+
+    fn poll_read(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        pin::Pin::new(&mut *self.stream.borrow_mut()).poll_read(cx, buffer)
+    }
+
+    fn poll_read_vectored(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffers: &mut [super::IoSliceMut<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        pin::Pin::new(&mut *self.stream.borrow_mut()).poll_read_vectored(cx, buffers)
+    }
+}
+
+impl<T> super::Write for WriteHalf<'_, T>
+where
+    T: super::Write + Unpin,
+{
+    type Error = T::Error;
+
+    fn poll_write(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        pin::Pin::new(&mut *self.stream.borrow_mut()).poll_write(cx, bytes)
+    }
+
+    fn poll_write_vectored(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffers: &[super::IoSlice<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        pin::Pin::new(&mut *self.stream.borrow_mut()).poll_write_vectored(cx, buffers)
+    }
+
+    fn poll_flush(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut *self.stream.borrow_mut()).poll_flush(cx)
+    }
+
+    fn poll_close(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        pin::Pin::new(&mut *self.stream.borrow_mut()).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn reads_and_writes_concurrently() {
+        futures::executor::block_on(async {
+            let mut pipe = io::Pipe::<4>::new();
+            let (first, mut second) = io::duplex(&mut pipe);
+            let mut split = io::Split::new(first);
+            let (mut reader, mut writer) = io::split(&mut split);
+            let mut received = [0; 11];
+
+            let reading = reader.read_exact(&mut received);
+            let writing = async {
+                writer.write_all(b"hello world").await?;
+                writer.shutdown().await
+            };
+            // The other end echoes everything back, so neither half gets ahead of the other by
+            // more than the size of the pipe
+            let echoing = async {
+                let mut buffer = [0; 4];
+                let mut total = 0;
+                loop {
+                    let n = second.read(&mut buffer).await?;
+                    if n == 0 {
+                        return Ok::<_, io::Error>(total);
+                    }
+                    second.write_all(&buffer[..n]).await?;
+                    total += n;
+                }
+            };
+
+            let (read, written, echoed) = futures::join!(reading, writing, echoing);
+            read.unwrap();
+            written.unwrap();
+            assert_eq!(echoed, Ok(11));
+            assert_eq!(&received, b"hello world");
+        });
+    }
+}
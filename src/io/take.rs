@@ -0,0 +1,94 @@
+use core::cmp;
+use core::pin;
+use core::task;
+
+/// A reader that reads at most a limited number of bytes from an underlying reader.
+///
+/// Once the limit has been reached, the reader behaves as if it has reached its end.
+#[derive(Debug)]
+pub struct Take<R> {
+    reader: R,
+    limit: u64,
+}
+
+pub fn take<R>(reader: R, limit: u64) -> Take<R>
+where
+    R: super::Read,
+{
+    Take { reader, limit }
+}
+
+impl<R> Take<R> {
+    /// The number of bytes that can still be read before the limit is reached.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Changes the number of bytes that can still be read before the limit is reached.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes this adapter, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> super::Read for Take<R>
+where
+    R: super::Read + Unpin,
+{
+    type Error = R::Error;
+
+    fn poll_read(
+        mut self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        let this = &mut *self;
+        if this.limit == 0 {
+            return task::Poll::Ready(Ok(0));
+        }
+
+        let max = cmp::min(buffer.len() as u64, this.limit) as usize;
+        let n = futures::ready!(pin::Pin::new(&mut this.reader).poll_read(cx, &mut buffer[..max]))?;
+        this.limit -= n as u64;
+        task::Poll::Ready(Ok(n))
+    }
+}
+
+impl<R> super::BufRead for Take<R>
+where
+    R: super::BufRead + Unpin,
+{
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<&[u8], Self::Error>> {
+        let this = self.get_mut();
+        if this.limit == 0 {
+            return task::Poll::Ready(Ok(&[]));
+        }
+
+        let available = futures::ready!(pin::Pin::new(&mut this.reader).poll_fill_buf(cx))?;
+        let max = cmp::min(available.len() as u64, this.limit) as usize;
+        task::Poll::Ready(Ok(&available[..max]))
+    }
+
+    fn consume(mut self: pin::Pin<&mut Self>, amount: usize) {
+        let amount = cmp::min(amount as u64, self.limit) as usize;
+        self.limit -= amount as u64;
+        pin::Pin::new(&mut self.reader).consume(amount);
+    }
+}
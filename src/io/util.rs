@@ -0,0 +1,151 @@
+use core::pin;
+use core::task;
+
+/// A writer that accepts and discards all data, created by [`sink`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sink {
+    _private: (),
+}
+
+/// A reader that is always at its end, created by [`empty`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Empty {
+    _private: (),
+}
+
+/// A reader that yields the same byte over and over again, created by [`repeat`].
+#[derive(Clone, Copy, Debug)]
+pub struct Repeat {
+    byte: u8,
+}
+
+/// Creates a writer that accepts and discards all data.
+pub fn sink() -> Sink {
+    Sink { _private: () }
+}
+
+/// Creates a reader that is always at its end.
+pub fn empty() -> Empty {
+    Empty { _private: () }
+}
+
+/// Creates a reader that yields `byte` over and over again.
+pub fn repeat(byte: u8) -> Repeat {
+    Repeat { byte }
+}
+
+impl super::Write for Sink {
+    type Error = super::Error;
+
+    fn poll_write(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        bytes: &[u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        task::Poll::Ready(Ok(bytes.len()))
+    }
+
+    fn poll_write_vectored(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        buffers: &[super::IoSlice<'_>],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        task::Poll::Ready(Ok(buffers.iter().map(|buffer| buffer.len()).sum()))
+    }
+
+    fn poll_flush(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl super::Read for Empty {
+    type Error = super::Error;
+
+    fn poll_read(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        _buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        task::Poll::Ready(Ok(0))
+    }
+}
+
+impl super::BufRead for Empty {
+    fn poll_fill_buf(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<&[u8], Self::Error>> {
+        task::Poll::Ready(Ok(&[]))
+    }
+
+    fn consume(self: pin::Pin<&mut Self>, _amount: usize) {}
+}
+
+impl super::Read for Repeat {
+    type Error = super::Error;
+
+    fn poll_read(
+        self: pin::Pin<&mut Self>,
+        _cx: &mut task::Context<'_>,
+        buffer: &mut [u8],
+    ) -> task::Poll<Result<usize, Self::Error>> {
+        for byte in buffer.iter_mut() {
+            *byte = self.byte;
+        }
+        task::Poll::Ready(Ok(buffer.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::prelude::*;
+
+    #[test]
+    fn sink_accepts_everything() {
+        futures::executor::block_on(async {
+            let mut sink = io::sink();
+            assert_eq!(sink.write(b"hello").await, Ok(5));
+            sink.write_all(&[0; 1024]).await.unwrap();
+            sink.shutdown().await.unwrap();
+        });
+    }
+
+    #[test]
+    fn empty_is_always_at_its_end() {
+        futures::executor::block_on(async {
+            let mut empty = io::empty();
+            let mut bytes = [0; 4];
+            assert_eq!(empty.read(&mut bytes).await, Ok(0));
+            assert_eq!(empty.read_line(&mut bytes).await, Ok(0));
+            assert_eq!(
+                empty.read_exact(&mut bytes).await,
+                Err(io::Error::UnexpectedEof)
+            );
+        });
+    }
+
+    #[test]
+    fn repeat_fills_every_buffer() {
+        futures::executor::block_on(async {
+            let mut repeat = io::repeat(b'x');
+            let mut bytes = [0; 4];
+            assert_eq!(repeat.read(&mut bytes).await, Ok(4));
+            assert_eq!(&bytes, b"xxxx");
+
+            let mut bytes = [0; 3];
+            assert_eq!(repeat.read(&mut bytes).await, Ok(3));
+            assert_eq!(&bytes, b"xxx");
+        });
+    }
+}
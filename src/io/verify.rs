@@ -0,0 +1,95 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Verify<'a, R, C>
+where
+    C: super::crc::Crc,
+{
+    reader: &'a mut super::CrcReader<R, C>,
+    endian: super::num::Endian,
+    bytes: <C::Value as super::num::Number>::Bytes,
+    position: usize,
+}
+
+pub fn verify<R, C>(
+    reader: &mut super::CrcReader<R, C>,
+    endian: super::num::Endian,
+) -> Verify<'_, R, C>
+where
+    R: super::Read + Unpin,
+    C: super::crc::Crc,
+{
+    let bytes = Default::default();
+    let position = 0;
+    Verify {
+        reader,
+        endian,
+        bytes,
+        position,
+    }
+}
+
+impl<R, C> future::Future for Verify<'_, R, C>
+where
+    R: super::Read + Unpin,
+    C: super::crc::Crc,
+{
+    type Output = Result<bool, R::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        use super::num::Number;
+        use super::ReadError;
+
+        let this = &mut *self;
+        while this.position < this.bytes.as_ref().len() {
+            let n = futures::ready!(pin::Pin::new(this.reader.get_mut())
+                .poll_read(cx, &mut this.bytes.as_mut()[this.position..]))?;
+            if n == 0 {
+                return task::Poll::Ready(Err(R::Error::eof()));
+            }
+            this.position += n;
+        }
+
+        let checksum = C::Value::from_bytes(this.bytes, this.endian);
+        task::Poll::Ready(Ok(checksum == this.reader.value()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use crate::io::crc::{self, Crc16};
+    use crate::io::num::Endian;
+    use crate::prelude::*;
+
+    #[test]
+    fn accepts_a_matching_checksum() {
+        futures::executor::block_on(async {
+            let input = *b"123456789\x37\x4b";
+            let crc = Crc16::bitwise(crc::CRC_16_MODBUS);
+            let mut reader = io::CrcReader::new(io::Cursor::new(input), crc);
+
+            let mut data = [0; 9];
+            reader.read_exact(&mut data).await.unwrap();
+
+            assert_eq!(reader.verify(Endian::Little).await, Ok(true));
+        });
+    }
+
+    #[test]
+    fn rejects_a_mismatching_checksum() {
+        futures::executor::block_on(async {
+            let input = *b"123456789\x37\x4b";
+            let crc = Crc16::bitwise(crc::CRC_16_MODBUS);
+            let mut reader = io::CrcReader::new(io::Cursor::new(input), crc);
+
+            let mut data = [0; 9];
+            reader.read_exact(&mut data).await.unwrap();
+
+            assert_eq!(reader.verify(Endian::Big).await, Ok(false));
+        });
+    }
+}
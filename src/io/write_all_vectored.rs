@@ -0,0 +1,47 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteAllVectored<'a, 'b, A: ?Sized> {
+    writer: &'a mut A,
+    buffers: &'a mut [super::IoSlice<'b>],
+}
+
+pub fn write_all_vectored<'a, 'b, A>(
+    writer: &'a mut A,
+    buffers: &'a mut [super::IoSlice<'b>],
+) -> WriteAllVectored<'a, 'b, A>
+where
+    A: super::Write + Unpin + ?Sized,
+{
+    WriteAllVectored { writer, buffers }
+}
+
+impl<A> future::Future for WriteAllVectored<'_, '_, A>
+where
+    A: super::Write + Unpin + ?Sized,
+{
+    type Output = Result<(), A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        use super::Write;
+        use super::WriteError;
+
+        let this = &mut *self;
+        // Drop any leading empty slices, so that writing only empty slices isn't an error
+        super::IoSlice::advance_slices(&mut this.buffers, 0);
+        while !this.buffers.is_empty() {
+            let n = futures::ready!(
+                pin::Pin::new(&mut this.writer).poll_write_vectored(cx, this.buffers)
+            )?;
+            if n == 0 {
+                return task::Poll::Ready(Err(A::Error::write_zero()));
+            }
+            super::IoSlice::advance_slices(&mut this.buffers, n);
+        }
+
+        task::Poll::Ready(Ok(()))
+    }
+}
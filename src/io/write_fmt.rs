@@ -0,0 +1,209 @@
+use core::fmt;
+use core::future;
+use core::pin;
+use core::task;
+
+/// The maximum number of bytes of formatted text that can be written at once.
+pub const CAPACITY: usize = 128;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteFmt<'a, A: ?Sized> {
+    writer: &'a mut A,
+    arguments: Option<fmt::Arguments<'a>>,
+    buffer: [u8; CAPACITY],
+    len: usize,
+    written: usize,
+}
+
+/// Collects formatted text, failing once it doesn't fit anymore.
+struct Buffer<'a> {
+    bytes: &'a mut [u8],
+    len: usize,
+}
+
+pub fn write_fmt<'a, A>(writer: &'a mut A, arguments: fmt::Arguments<'a>) -> WriteFmt<'a, A>
+where
+    A: super::Write + Unpin + ?Sized,
+{
+    let arguments = Some(arguments);
+    let buffer = [0; CAPACITY];
+    let len = 0;
+    let written = 0;
+    WriteFmt {
+        writer,
+        arguments,
+        buffer,
+        len,
+        written,
+    }
+}
+
+impl<A> future::Future for WriteFmt<'_, A>
+where
+    A: super::Write + Unpin + ?Sized,
+{
+    type Output = Result<(), A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        use super::WriteError;
+
+        let this = &mut *self;
+
+        // Format everything up front, so that the arguments are only evaluated once, and nothing
+        // is written if the text doesn't fit.
+        if let Some(arguments) = this.arguments.take() {
+            let mut buffer = Buffer {
+                bytes: &mut this.buffer,
+                len: 0,
+            };
+            if fmt::write(&mut buffer, arguments).is_err() {
+                return task::Poll::Ready(Err(A::Error::write_zero()));
+            }
+            this.len = buffer.len;
+        }
+
+        while this.written < this.len {
+            let n = futures::ready!(pin::Pin::new(&mut *this.writer)
+                .poll_write(cx, &this.buffer[this.written..this.len]))?;
+            if n == 0 {
+                return task::Poll::Ready(Err(A::Error::write_zero()));
+            }
+            this.written += n;
+        }
+
+        task::Poll::Ready(Ok(()))
+    }
+}
+
+impl fmt::Write for Buffer<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let bytes = s.as_bytes();
+        let end = self.len + bytes.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+
+        self.bytes[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Writes formatted text to an [`io::Write`](crate::io::Write), returning a future that resolves
+/// once all of the text has been written.
+///
+/// This works like [`core::write!`], except that errors come from the writer rather than being
+/// `fmt::Error`s.  Like with `format_args!`, the future borrows the formatted values, so it needs
+/// to be awaited in the same statement:
+///
+/// ```ignore
+/// uwrite!(uart, "temperature: {} °C", celsius).await?;
+/// ```
+///
+/// The text is formatted into a buffer of [`CAPACITY`](crate::io::write_fmt::CAPACITY) bytes
+/// before any of it is written.  Longer text, or a formatting trait implementation that returns an
+/// error, results in a `write_zero` error without writing anything.
+#[macro_export]
+macro_rules! uwrite {
+    ($writer:expr, $($arg:tt)*) => {{
+        use $crate::io::WriteExt as _;
+        $writer.write_fmt(::core::format_args!($($arg)*))
+    }};
+}
+
+/// Writes formatted text followed by a newline to an [`io::Write`](crate::io::Write), returning a
+/// future that resolves once all of the text has been written.
+///
+/// See [`uwrite!`] for details.
+#[macro_export]
+macro_rules! uwriteln {
+    ($writer:expr $(,)?) => {
+        $crate::uwrite!($writer, "\n")
+    };
+    ($writer:expr, $($arg:tt)*) => {{
+        use $crate::io::WriteExt as _;
+        $writer.write_fmt(::core::format_args!("{}\n", ::core::format_args!($($arg)*)))
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io;
+    use core::fmt;
+
+    struct Failing;
+
+    impl fmt::Display for Failing {
+        fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    #[test]
+    fn writes_formatted_text() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 16];
+            let mut cursor = io::Cursor::new(&mut buffer[..]);
+
+            crate::uwrite!(cursor, "{}-{}", 1, "two").await.unwrap();
+            crate::uwriteln!(cursor, "!").await.unwrap();
+
+            assert_eq!(&buffer[..7], b"1-two!\n");
+        });
+    }
+
+    #[test]
+    fn writes_text_that_fills_the_buffer() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; super::CAPACITY];
+            let mut cursor = io::Cursor::new(&mut buffer[..]);
+
+            let width = super::CAPACITY;
+            crate::uwrite!(cursor, "{:>1$}", "x", width).await.unwrap();
+
+            assert_eq!(cursor.position(), super::CAPACITY);
+            assert_eq!(buffer[super::CAPACITY - 1], b'x');
+        });
+    }
+
+    #[test]
+    fn rejects_text_that_overflows_the_buffer() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 2 * super::CAPACITY];
+            let mut cursor = io::Cursor::new(&mut buffer[..]);
+
+            let width = super::CAPACITY + 1;
+            let result = crate::uwrite!(cursor, "{:>1$}", "x", width).await;
+
+            assert_eq!(result, Err(io::Error::WriteZero));
+            assert_eq!(cursor.position(), 0);
+        });
+    }
+
+    #[test]
+    fn rejects_failing_formatting() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 16];
+            let mut cursor = io::Cursor::new(&mut buffer[..]);
+
+            let result = crate::uwrite!(cursor, "a{}b", Failing).await;
+
+            assert_eq!(result, Err(io::Error::WriteZero));
+            assert_eq!(cursor.position(), 0);
+        });
+    }
+
+    #[test]
+    fn reports_a_full_writer() {
+        futures::executor::block_on(async {
+            let mut buffer = [0; 4];
+            let mut cursor = io::Cursor::new(&mut buffer[..]);
+
+            let result = crate::uwrite!(cursor, "hello").await;
+
+            assert_eq!(result, Err(io::Error::WriteZero));
+            assert_eq!(&buffer, b"hell");
+        });
+    }
+}
@@ -0,0 +1,52 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteNum<'a, A: ?Sized, T>
+where
+    T: super::num::Number,
+{
+    writer: &'a mut A,
+    bytes: T::Bytes,
+    position: usize,
+}
+
+pub fn write_num<A, T>(writer: &mut A, value: T, endian: super::num::Endian) -> WriteNum<'_, A, T>
+where
+    A: super::Write + Unpin + ?Sized,
+    T: super::num::Number,
+{
+    let bytes = value.to_bytes(endian);
+    let position = 0;
+    WriteNum {
+        writer,
+        bytes,
+        position,
+    }
+}
+
+impl<A, T> future::Future for WriteNum<'_, A, T>
+where
+    A: super::Write + Unpin + ?Sized,
+    T: super::num::Number,
+{
+    type Output = Result<(), A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        use super::WriteError;
+
+        let this = &mut *self;
+        while this.position < this.bytes.as_ref().len() {
+            let n = futures::ready!(pin::Pin::new(&mut *this.writer)
+                .poll_write(cx, &this.bytes.as_ref()[this.position..]))?;
+            if n == 0 {
+                return task::Poll::Ready(Err(A::Error::write_zero()));
+            }
+            this.position += n;
+        }
+
+        task::Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,32 @@
+use core::future;
+use core::pin;
+use core::task;
+
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct WriteVectored<'a, 'b, A: ?Sized> {
+    writer: &'a mut A,
+    buffers: &'a [super::IoSlice<'b>],
+}
+
+pub fn write_vectored<'a, 'b, A>(
+    writer: &'a mut A,
+    buffers: &'a [super::IoSlice<'b>],
+) -> WriteVectored<'a, 'b, A>
+where
+    A: super::Write + Unpin + ?Sized,
+{
+    WriteVectored { writer, buffers }
+}
+
+impl<A> future::Future for WriteVectored<'_, '_, A>
+where
+    A: super::Write + Unpin + ?Sized,
+{
+    type Output = Result<usize, A::Error>;
+
+    fn poll(mut self: pin::Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = &mut *self;
+        pin::Pin::new(&mut *this.writer).poll_write_vectored(cx, this.buffers)
+    }
+}
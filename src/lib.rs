@@ -21,11 +21,11 @@
 //!     example to check that a pin is used only once.  It turns out to be super tricky to do
 //!     granular ownership mapping of device registers at compile time (this has been done in
 //!     [`drone-os`](https://www.drone-os.com/)), and instead we opt to do some checks at runtime
-//!     (e.g. `Option::take`).  This wastes a dozen or so instructions at startup, which is a
-//!     one-time cost.
+//!     (using a [`registry`] that also tracks who took what).  This wastes a dozen or so
+//!     instructions at startup, which is a one-time cost.
 //!   * All APIs are async-first, so that code won't have to block and we can be power efficient.
-//!     This does require an executor, and one can be made that doesn't require `alloc`, yet to be
-//!     written.
+//!     This does require an executor, and the [`executor`] module provides one that doesn't require
+//!     `alloc`.
 //!   * The crate uses its own HAL-like traits for e.g. `OutputPin` or `I2cRead` to enable async
 //!     APIs as well as smooth over any incompatibilities between `embedded_hal::gpio::v1` and
 //!     `embedded_hal::gpio::v2` etc.
@@ -82,11 +82,17 @@
 )]
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod codec;
+pub mod executor;
 pub mod gpio;
 pub mod i2c;
 pub mod io;
 pub mod platform;
 pub mod prelude;
+pub mod registry;
 pub mod serial;
 pub mod specs;
 pub mod spi;
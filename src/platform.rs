@@ -1,18 +1,41 @@
+use crate::executor;
 use core::fmt;
 use core::future;
 use core::task;
 
 pub mod initialize;
+pub mod recovery;
 
 pub trait Platform: fmt::Debug + Sized {
-    type Error;
+    type Error: fmt::Debug;
 
+    /// Runs the application on this platform, panicking with the error if it fails.
     fn main<I, F>(run: I) -> !
     where
         I: FnOnce(Self) -> F,
-        F: future::Future<Output = Result<(), Self::Error>>;
+        F: future::Future<Output = Result<(), Self::Error>>,
+    {
+        let mut run = Some(run);
+        let run = move |platform| {
+            let run = run.take().expect("the application was restarted");
+            run(platform)
+        };
+        Self::main_with(run, recovery::report)
+    }
+
+    /// Runs the application on this platform, letting `handler` decide what happens if it fails.
+    ///
+    /// See the [`recovery`] module for more information.
+    fn main_with<I, F, H>(run: I, handler: H) -> !
+    where
+        I: FnMut(Self) -> F,
+        F: future::Future<Output = Result<(), Self::Error>>,
+        H: recovery::Handler<Self::Error>;
 
     fn poll_initialize(cx: &mut task::Context<'_>) -> task::Poll<Result<Self, Self::Error>>;
+
+    /// Returns a handle for spawning additional tasks onto the executor running this platform.
+    fn spawner(&self) -> executor::Spawner;
 }
 
 pub trait PlatformExt: Platform {
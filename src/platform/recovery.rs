@@ -0,0 +1,200 @@
+//! Deciding what happens when an application fails.
+//!
+//! When the application future passed to [`Platform::main_with`] returns an error, or the platform
+//! itself fails to initialize, the error is wrapped in a [`Failure`] and handed to a [`Handler`].
+//! The handler can report the error however it sees fit (for example over a serial port), and then
+//! decides on a [`Recovery`] that the platform will carry out.
+//!
+//! Any `FnMut(Failure<E>) -> impl Future<Output = Recovery>` is a handler, so for example an
+//! application that resets the device after failing three times in a row can be written as:
+//!
+//! ```ignore
+//! MyPlatform::main_with(app, |failure: Failure<_>| async move {
+//!     if failure.attempt() < 3 {
+//!         Recovery::Restart
+//!     } else {
+//!         Recovery::Reset
+//!     }
+//! })
+//! ```
+//!
+//! [`Platform::main_with`]: super::Platform::main_with
+use super::Platform;
+use super::PlatformExt;
+use core::fmt;
+use core::future;
+
+/// The stage of the platform's lifecycle during which a failure happened.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Stage {
+    /// The platform failed to initialize, so the application was never started.
+    Initialize,
+    /// The application returned an error.
+    Run,
+}
+
+/// What the platform should do after the application has failed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Recovery {
+    /// Initialize the platform again and restart the application future.
+    ///
+    /// Not all platforms can be initialized more than once.  Those carry out a [`Recovery::Reset`]
+    /// instead, which restarts the application as well.
+    Restart,
+    /// Reset the whole system, as if the reset button had been pressed.
+    Reset,
+    /// Stop doing anything at all, until the system is reset externally.
+    Halt,
+}
+
+/// A failure of the application (or of the platform initialization) that was handed to a
+/// [`Handler`].
+#[derive(Clone, Copy, Debug)]
+pub struct Failure<E> {
+    stage: Stage,
+    attempt: usize,
+    error: E,
+}
+
+/// A hook that gets to report application failures, and decide how to recover from them.
+pub trait Handler<E> {
+    /// The future that reports the failure and resolves to the recovery to carry out.
+    type Handle: future::Future<Output = Recovery>;
+
+    /// Handles the specified failure.
+    fn handle(&mut self, failure: Failure<E>) -> Self::Handle;
+}
+
+impl<E> Failure<E> {
+    /// Creates a new failure that happened during the specified stage of the specified attempt.
+    pub fn new(stage: Stage, attempt: usize, error: E) -> Self {
+        Failure {
+            stage,
+            attempt,
+            error,
+        }
+    }
+
+    /// The stage during which the failure happened.
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// The number of the attempt that failed, starting at 1 and increasing by one for every
+    /// [`Recovery::Restart`].
+    pub fn attempt(&self) -> usize {
+        self.attempt
+    }
+
+    /// The error that caused the failure.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Consumes this failure, returning the error that caused it.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+impl<E> fmt::Display for Failure<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stage = match self.stage {
+            Stage::Initialize => "the platform failed to initialize",
+            Stage::Run => "the application failed",
+        };
+        write!(f, "{} (attempt {}): {:?}", stage, self.attempt, self.error)
+    }
+}
+
+impl<E, F, H> Handler<E> for F
+where
+    F: FnMut(Failure<E>) -> H,
+    H: future::Future<Output = Recovery>,
+{
+    type Handle = H;
+
+    fn handle(&mut self, failure: Failure<E>) -> Self::Handle {
+        self(failure)
+    }
+}
+
+/// A handler that reports every failure by panicking with it.
+///
+/// This is what [`Platform::main`] uses, and is a reasonable default since panic handlers like
+/// `panic-probe` or `panic-semihosting` already know how to get a message off the device; the
+/// panic handler then decides what happens next.
+///
+/// [`Platform::main`]: super::Platform::main
+pub fn report<E>(failure: Failure<E>) -> futures::future::Ready<Recovery>
+where
+    E: fmt::Debug,
+{
+    panic!("{}", failure)
+}
+
+/// Initializes the platform and runs the application, restarting it for as long as `handler` asks
+/// for it.
+///
+/// The `prepare` function is called on every newly initialized platform before it is handed to the
+/// application, which is where platform implementations can hook up e.g. their spawner.
+///
+/// Returns `Ok(())` once the application completes successfully, or `Err` with the recovery that
+/// the platform should carry out, which is always either [`Recovery::Reset`] or
+/// [`Recovery::Halt`].
+///
+/// Every attempt runs as part of the same future, so tasks spawned by a failed attempt keep running.
+/// Platforms that can give every attempt a fresh executor should call [`attempt`] in a loop instead.
+pub async fn supervise<P, I, F, H>(
+    mut run: I,
+    mut handler: H,
+    mut prepare: impl FnMut(&mut P),
+) -> Result<(), Recovery>
+where
+    P: Platform,
+    I: FnMut(P) -> F,
+    F: future::Future<Output = Result<(), P::Error>>,
+    H: Handler<P::Error>,
+{
+    let mut number = 1;
+    loop {
+        match attempt(number, &mut run, &mut handler, &mut prepare).await {
+            Err(Recovery::Restart) => number += 1,
+            result => return result,
+        }
+    }
+}
+
+/// Initializes the platform and runs the application once, as attempt number `number`.
+///
+/// Returns `Ok(())` if the application completes successfully, or `Err` with the recovery that
+/// `handler` chose if it fails.  The caller is responsible for starting the next attempt if that
+/// is a [`Recovery::Restart`].
+pub async fn attempt<P, I, F, H>(
+    number: usize,
+    run: &mut I,
+    handler: &mut H,
+    prepare: impl FnOnce(&mut P),
+) -> Result<(), Recovery>
+where
+    P: Platform,
+    I: FnMut(P) -> F,
+    F: future::Future<Output = Result<(), P::Error>>,
+    H: Handler<P::Error>,
+{
+    let failure = match P::initialize().await {
+        Ok(mut platform) => {
+            prepare(&mut platform);
+            match run(platform).await {
+                Ok(()) => return Ok(()),
+                Err(error) => Failure::new(Stage::Run, number, error),
+            }
+        }
+        Err(error) => Failure::new(Stage::Initialize, number, error),
+    };
+
+    Err(handler.handle(failure).await)
+}
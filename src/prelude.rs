@@ -1,4 +1,5 @@
 pub use crate::gpio::InputPinExt;
+pub use crate::gpio::IntoFlexPin;
 pub use crate::gpio::IntoFloatingInputPin;
 pub use crate::gpio::IntoOpenDrainOutputPin;
 pub use crate::gpio::IntoPullDownInputPin;
@@ -8,6 +9,7 @@ pub use crate::gpio::OutputPinExt;
 pub use crate::i2c::I2cBusMappingExt;
 pub use crate::i2c::I2cReadExt;
 pub use crate::i2c::I2cWriteExt;
+pub use crate::io::BufReadExt;
 pub use crate::io::ReadExt;
 pub use crate::io::WriteExt;
 pub use crate::platform::Platform;
@@ -0,0 +1,9 @@
+//! Keeping track of which peripherals have been taken, and by whom.
+//!
+//! Platforms record every pin, timer or bus that is taken from them in a [`Registry`], so that
+//! taking something twice fails with an [`AlreadyTaken`] error naming both claimants.  Resources
+//! can be released again, after which they can be taken by someone else.
+pub use core::registry::AlreadyTaken;
+pub use core::registry::ClaimError;
+pub use core::registry::Registry;
+pub use core::registry::Resource;
@@ -1,6 +1,7 @@
 use crate::gpio;
 use crate::i2c;
 use crate::platform;
+use crate::registry;
 
 /// A platform that conforms to the [Adafruit Feather specification](https://learn.adafruit.com/adafruit-feather/feather-specification).
 ///
@@ -20,7 +21,19 @@ use crate::platform;
 ///   * `P0` is mapped to something custom depending on the specific feather, usually a GPIO.
 ///
 /// Additionally, it is guaranteed that there is one main LED bound to a pin, but which one it is
-/// is left unspecified.
+/// is left unspecified.  Setting the main LED high always turns it on: platforms where the LED is
+/// lit by driving its pin low hand it out as an [`Inverted`](gpio::Inverted) pin.
+///
+/// # Taking pins
+///
+/// Every pin can be taken once, using either `take_*` (which panics if the pin is already taken)
+/// or `try_take_*` (which returns a [`ClaimError`](registry::ClaimError), such as an
+/// [`AlreadyTaken`](registry::AlreadyTaken) error naming both claimants).  The main I²C bus is bound to `SDA`/`SCL`, and the main LED is often bound to one
+/// of the other pins, so taking them also counts as taking those pins.  A pin can be handed back
+/// using `release_*`, after which it can be taken again.
+///
+/// Platforms usually implement this trait using the [`feather_board!`](crate::feather_board)
+/// macro, which generates all of these methods from a table of pins.
 ///
 /// The pins are placed roughly according to this illustration:
 ///
@@ -72,31 +85,760 @@ pub trait Feather: platform::Platform {
     type A1: gpio::IntoFloatingInputPin<Error = Self::Error>;
     type A0: gpio::IntoFloatingInputPin<Error = Self::Error>;
 
-    fn take_main_led(&mut self) -> Self::MainLed;
-
-    fn take_main_i2c(
-        &mut self,
-    ) -> <Self::MainI2cMapping as i2c::I2cBusMapping<Self::SDA, Self::SCL>>::Bus;
-
-    fn take_sda(&mut self) -> Self::SDA;
-    fn take_scl(&mut self) -> Self::SCL;
-    fn take_d2(&mut self) -> Self::D2;
-    fn take_d3(&mut self) -> Self::D3;
-    fn take_d4(&mut self) -> Self::D4;
-    fn take_d5(&mut self) -> Self::D5;
-    fn take_d6(&mut self) -> Self::D6;
-    fn take_d7(&mut self) -> Self::D7;
-    fn take_d8(&mut self) -> Self::D8;
-    fn take_p0(&mut self) -> Self::P0;
-    fn take_tx(&mut self) -> Self::TX;
-    fn take_rx(&mut self) -> Self::RX;
-    fn take_miso(&mut self) -> Self::MISO;
-    fn take_mosi(&mut self) -> Self::MOSI;
-    fn take_sck(&mut self) -> Self::SCK;
-    fn take_a5(&mut self) -> Self::A5;
-    fn take_a4(&mut self) -> Self::A4;
-    fn take_a3(&mut self) -> Self::A3;
-    fn take_a2(&mut self) -> Self::A2;
-    fn take_a1(&mut self) -> Self::A1;
-    fn take_a0(&mut self) -> Self::A0;
+    /// Takes the main LED, panicking if it (or the pin it is bound to) is already taken.
+    fn take_main_led(&mut self) -> Self::MainLed {
+        self.try_take_main_led().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Takes the main I²C bus, panicking if any of its pins are already taken.
+    fn take_main_i2c(&mut self) -> MainI2cBus<Self> {
+        self.try_take_main_i2c().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_sda(&mut self) -> Self::SDA {
+        self.try_take_sda().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_scl(&mut self) -> Self::SCL {
+        self.try_take_scl().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_d2(&mut self) -> Self::D2 {
+        self.try_take_d2().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_d3(&mut self) -> Self::D3 {
+        self.try_take_d3().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_d4(&mut self) -> Self::D4 {
+        self.try_take_d4().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_d5(&mut self) -> Self::D5 {
+        self.try_take_d5().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_d6(&mut self) -> Self::D6 {
+        self.try_take_d6().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_d7(&mut self) -> Self::D7 {
+        self.try_take_d7().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_d8(&mut self) -> Self::D8 {
+        self.try_take_d8().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_p0(&mut self) -> Self::P0 {
+        self.try_take_p0().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_tx(&mut self) -> Self::TX {
+        self.try_take_tx().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_rx(&mut self) -> Self::RX {
+        self.try_take_rx().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_miso(&mut self) -> Self::MISO {
+        self.try_take_miso().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_mosi(&mut self) -> Self::MOSI {
+        self.try_take_mosi().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_sck(&mut self) -> Self::SCK {
+        self.try_take_sck().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_a5(&mut self) -> Self::A5 {
+        self.try_take_a5().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_a4(&mut self) -> Self::A4 {
+        self.try_take_a4().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_a3(&mut self) -> Self::A3 {
+        self.try_take_a3().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_a2(&mut self) -> Self::A2 {
+        self.try_take_a2().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_a1(&mut self) -> Self::A1 {
+        self.try_take_a1().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn take_a0(&mut self) -> Self::A0 {
+        self.try_take_a0().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_take_main_led(&mut self) -> Result<Self::MainLed, registry::ClaimError>;
+    fn try_take_main_i2c(&mut self) -> Result<MainI2cBus<Self>, registry::ClaimError>;
+    fn try_take_sda(&mut self) -> Result<Self::SDA, registry::ClaimError>;
+    fn try_take_scl(&mut self) -> Result<Self::SCL, registry::ClaimError>;
+    fn try_take_d2(&mut self) -> Result<Self::D2, registry::ClaimError>;
+    fn try_take_d3(&mut self) -> Result<Self::D3, registry::ClaimError>;
+    fn try_take_d4(&mut self) -> Result<Self::D4, registry::ClaimError>;
+    fn try_take_d5(&mut self) -> Result<Self::D5, registry::ClaimError>;
+    fn try_take_d6(&mut self) -> Result<Self::D6, registry::ClaimError>;
+    fn try_take_d7(&mut self) -> Result<Self::D7, registry::ClaimError>;
+    fn try_take_d8(&mut self) -> Result<Self::D8, registry::ClaimError>;
+    fn try_take_p0(&mut self) -> Result<Self::P0, registry::ClaimError>;
+    fn try_take_tx(&mut self) -> Result<Self::TX, registry::ClaimError>;
+    fn try_take_rx(&mut self) -> Result<Self::RX, registry::ClaimError>;
+    fn try_take_miso(&mut self) -> Result<Self::MISO, registry::ClaimError>;
+    fn try_take_mosi(&mut self) -> Result<Self::MOSI, registry::ClaimError>;
+    fn try_take_sck(&mut self) -> Result<Self::SCK, registry::ClaimError>;
+    fn try_take_a5(&mut self) -> Result<Self::A5, registry::ClaimError>;
+    fn try_take_a4(&mut self) -> Result<Self::A4, registry::ClaimError>;
+    fn try_take_a3(&mut self) -> Result<Self::A3, registry::ClaimError>;
+    fn try_take_a2(&mut self) -> Result<Self::A2, registry::ClaimError>;
+    fn try_take_a1(&mut self) -> Result<Self::A1, registry::ClaimError>;
+    fn try_take_a0(&mut self) -> Result<Self::A0, registry::ClaimError>;
+
+    fn release_main_led(&mut self, main_led: Self::MainLed);
+    fn release_sda(&mut self, sda: Self::SDA);
+    fn release_scl(&mut self, scl: Self::SCL);
+    fn release_d2(&mut self, d2: Self::D2);
+    fn release_d3(&mut self, d3: Self::D3);
+    fn release_d4(&mut self, d4: Self::D4);
+    fn release_d5(&mut self, d5: Self::D5);
+    fn release_d6(&mut self, d6: Self::D6);
+    fn release_d7(&mut self, d7: Self::D7);
+    fn release_d8(&mut self, d8: Self::D8);
+    fn release_p0(&mut self, p0: Self::P0);
+    fn release_tx(&mut self, tx: Self::TX);
+    fn release_rx(&mut self, rx: Self::RX);
+    fn release_miso(&mut self, miso: Self::MISO);
+    fn release_mosi(&mut self, mosi: Self::MOSI);
+    fn release_sck(&mut self, sck: Self::SCK);
+    fn release_a5(&mut self, a5: Self::A5);
+    fn release_a4(&mut self, a4: Self::A4);
+    fn release_a3(&mut self, a3: Self::A3);
+    fn release_a2(&mut self, a2: Self::A2);
+    fn release_a1(&mut self, a1: Self::A1);
+    fn release_a0(&mut self, a0: Self::A0);
+}
+
+/// The type of the main I²C bus of a [`Feather`] platform.
+pub type MainI2cBus<P> = <<P as Feather>::MainI2cMapping as i2c::I2cBusMapping<
+    <P as Feather>::SDA,
+    <P as Feather>::SCL,
+>>::Bus;
+
+/// Implements [`Feather`] for a platform, based on a table that maps every pin of the spec to a
+/// physical pin and the type that represents it.
+///
+/// The physical pins are written as `port::pin`, and are handed to two macros supplied by the
+/// platform: `take!(self, port::pin, claimant)` should claim the pin and evaluate to a
+/// `Result<Pin, ClaimError>`, and `release!(self, port::pin, pin)` should hand the pin back.
+/// The `pin` idents must be unique across all ports (e.g. `p1_12` rather than `p12`), since using
+/// the same physical pin twice is rejected at compile time.
+///
+/// The main LED is either an explicit alias of one of the other pins (`main_led => d7;`), or a
+/// dedicated pin of its own (`main_led = port::pin: Type;`).  Either form can be followed by
+/// `as active_low` (e.g. `main_led => d7 as active_low;`) if the LED is lit by driving its pin low,
+/// which makes the main LED an [`Inverted`](crate::gpio::Inverted) pin.  `as active_high` is the
+/// default, and may be written out to make that explicit.  The main I²C bus is taken by an
+/// inherent method of the platform that is named in the table.  The rows have to be written in
+/// this order:
+///
+/// ```ignore
+/// embedded_platform::feather_board! {
+///     impl Feather for MyBoard {
+///         take = take_pin;
+///         release = release_pin;
+///         main_led => d7;
+///         main_i2c: i2c::I2cMapping<Self::SDA, Self::SCL> => try_take_main_i2c_bus;
+///
+///         sda = p0::p0_26: gpio::Pin<p0::P0_26<Input<Floating>>>;
+///         scl = p0::p0_27: gpio::Pin<p0::P0_27<Input<Floating>>>;
+///         // ... d2 to d8, p0, tx, rx, miso, mosi, sck, a5 to a1 ...
+///         a0 = p0::p0_03: gpio::Pin<p0::P0_03<Input<Floating>>>;
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! feather_board {
+    (
+        impl Feather for $platform:ty {
+            take = $take:ident;
+            release = $release:ident;
+            main_led $main_led_kind:tt $main_led:ident $(:: $main_led_pin:ident : $main_led_ty:ty)? $(as $main_led_polarity:ident)?;
+            main_i2c: $main_i2c_mapping:ty => $main_i2c:ident;
+
+            sda = $sda_port:ident :: $sda_pin:ident : $sda_ty:ty;
+            scl = $scl_port:ident :: $scl_pin:ident : $scl_ty:ty;
+            d2 = $d2_port:ident :: $d2_pin:ident : $d2_ty:ty;
+            d3 = $d3_port:ident :: $d3_pin:ident : $d3_ty:ty;
+            d4 = $d4_port:ident :: $d4_pin:ident : $d4_ty:ty;
+            d5 = $d5_port:ident :: $d5_pin:ident : $d5_ty:ty;
+            d6 = $d6_port:ident :: $d6_pin:ident : $d6_ty:ty;
+            d7 = $d7_port:ident :: $d7_pin:ident : $d7_ty:ty;
+            d8 = $d8_port:ident :: $d8_pin:ident : $d8_ty:ty;
+            p0 = $p0_port:ident :: $p0_pin:ident : $p0_ty:ty;
+            tx = $tx_port:ident :: $tx_pin:ident : $tx_ty:ty;
+            rx = $rx_port:ident :: $rx_pin:ident : $rx_ty:ty;
+            miso = $miso_port:ident :: $miso_pin:ident : $miso_ty:ty;
+            mosi = $mosi_port:ident :: $mosi_pin:ident : $mosi_ty:ty;
+            sck = $sck_port:ident :: $sck_pin:ident : $sck_ty:ty;
+            a5 = $a5_port:ident :: $a5_pin:ident : $a5_ty:ty;
+            a4 = $a4_port:ident :: $a4_pin:ident : $a4_ty:ty;
+            a3 = $a3_port:ident :: $a3_pin:ident : $a3_ty:ty;
+            a2 = $a2_port:ident :: $a2_pin:ident : $a2_ty:ty;
+            a1 = $a1_port:ident :: $a1_pin:ident : $a1_ty:ty;
+            a0 = $a0_port:ident :: $a0_pin:ident : $a0_ty:ty;
+        }
+    ) => {
+        const _: () = {
+            // Using the same physical pin twice results in a duplicate variant.
+            #[allow(dead_code, non_camel_case_types)]
+            enum PhysicalPins {
+                $sda_pin,
+                $scl_pin,
+                $d2_pin,
+                $d3_pin,
+                $d4_pin,
+                $d5_pin,
+                $d6_pin,
+                $d7_pin,
+                $d8_pin,
+                $p0_pin,
+                $tx_pin,
+                $rx_pin,
+                $miso_pin,
+                $mosi_pin,
+                $sck_pin,
+                $a5_pin,
+                $a4_pin,
+                $a3_pin,
+                $a2_pin,
+                $a1_pin,
+                $a0_pin,
+                $($main_led_pin,)?
+            }
+
+            // Indexing these helper traits by pin name is what makes `main_led => d7` work.  They
+            // are only `pub` so that they may show up in the associated types below.
+            #[allow(non_camel_case_types)]
+            pub trait Pins {
+                type sda;
+                type scl;
+                type d2;
+                type d3;
+                type d4;
+                type d5;
+                type d6;
+                type d7;
+                type d8;
+                type p0;
+                type tx;
+                type rx;
+                type miso;
+                type mosi;
+                type sck;
+                type a5;
+                type a4;
+                type a3;
+                type a2;
+                type a1;
+                type a0;
+
+                fn sda(&mut self, claimant: &'static str) -> Result<Self::sda, $crate::registry::ClaimError>;
+                fn scl(&mut self, claimant: &'static str) -> Result<Self::scl, $crate::registry::ClaimError>;
+                fn d2(&mut self, claimant: &'static str) -> Result<Self::d2, $crate::registry::ClaimError>;
+                fn d3(&mut self, claimant: &'static str) -> Result<Self::d3, $crate::registry::ClaimError>;
+                fn d4(&mut self, claimant: &'static str) -> Result<Self::d4, $crate::registry::ClaimError>;
+                fn d5(&mut self, claimant: &'static str) -> Result<Self::d5, $crate::registry::ClaimError>;
+                fn d6(&mut self, claimant: &'static str) -> Result<Self::d6, $crate::registry::ClaimError>;
+                fn d7(&mut self, claimant: &'static str) -> Result<Self::d7, $crate::registry::ClaimError>;
+                fn d8(&mut self, claimant: &'static str) -> Result<Self::d8, $crate::registry::ClaimError>;
+                fn p0(&mut self, claimant: &'static str) -> Result<Self::p0, $crate::registry::ClaimError>;
+                fn tx(&mut self, claimant: &'static str) -> Result<Self::tx, $crate::registry::ClaimError>;
+                fn rx(&mut self, claimant: &'static str) -> Result<Self::rx, $crate::registry::ClaimError>;
+                fn miso(&mut self, claimant: &'static str) -> Result<Self::miso, $crate::registry::ClaimError>;
+                fn mosi(&mut self, claimant: &'static str) -> Result<Self::mosi, $crate::registry::ClaimError>;
+                fn sck(&mut self, claimant: &'static str) -> Result<Self::sck, $crate::registry::ClaimError>;
+                fn a5(&mut self, claimant: &'static str) -> Result<Self::a5, $crate::registry::ClaimError>;
+                fn a4(&mut self, claimant: &'static str) -> Result<Self::a4, $crate::registry::ClaimError>;
+                fn a3(&mut self, claimant: &'static str) -> Result<Self::a3, $crate::registry::ClaimError>;
+                fn a2(&mut self, claimant: &'static str) -> Result<Self::a2, $crate::registry::ClaimError>;
+                fn a1(&mut self, claimant: &'static str) -> Result<Self::a1, $crate::registry::ClaimError>;
+                fn a0(&mut self, claimant: &'static str) -> Result<Self::a0, $crate::registry::ClaimError>;
+            }
+
+            pub trait ReleasePins: Pins {
+                fn sda(&mut self, pin: <Self as Pins>::sda);
+                fn scl(&mut self, pin: <Self as Pins>::scl);
+                fn d2(&mut self, pin: <Self as Pins>::d2);
+                fn d3(&mut self, pin: <Self as Pins>::d3);
+                fn d4(&mut self, pin: <Self as Pins>::d4);
+                fn d5(&mut self, pin: <Self as Pins>::d5);
+                fn d6(&mut self, pin: <Self as Pins>::d6);
+                fn d7(&mut self, pin: <Self as Pins>::d7);
+                fn d8(&mut self, pin: <Self as Pins>::d8);
+                fn p0(&mut self, pin: <Self as Pins>::p0);
+                fn tx(&mut self, pin: <Self as Pins>::tx);
+                fn rx(&mut self, pin: <Self as Pins>::rx);
+                fn miso(&mut self, pin: <Self as Pins>::miso);
+                fn mosi(&mut self, pin: <Self as Pins>::mosi);
+                fn sck(&mut self, pin: <Self as Pins>::sck);
+                fn a5(&mut self, pin: <Self as Pins>::a5);
+                fn a4(&mut self, pin: <Self as Pins>::a4);
+                fn a3(&mut self, pin: <Self as Pins>::a3);
+                fn a2(&mut self, pin: <Self as Pins>::a2);
+                fn a1(&mut self, pin: <Self as Pins>::a1);
+                fn a0(&mut self, pin: <Self as Pins>::a0);
+            }
+
+            impl Pins for $platform {
+                type sda = $sda_ty;
+                type scl = $scl_ty;
+                type d2 = $d2_ty;
+                type d3 = $d3_ty;
+                type d4 = $d4_ty;
+                type d5 = $d5_ty;
+                type d6 = $d6_ty;
+                type d7 = $d7_ty;
+                type d8 = $d8_ty;
+                type p0 = $p0_ty;
+                type tx = $tx_ty;
+                type rx = $rx_ty;
+                type miso = $miso_ty;
+                type mosi = $mosi_ty;
+                type sck = $sck_ty;
+                type a5 = $a5_ty;
+                type a4 = $a4_ty;
+                type a3 = $a3_ty;
+                type a2 = $a2_ty;
+                type a1 = $a1_ty;
+                type a0 = $a0_ty;
+
+                fn sda(&mut self, claimant: &'static str) -> Result<Self::sda, $crate::registry::ClaimError> {
+                    $take!(self, $sda_port::$sda_pin, claimant)
+                }
+
+                fn scl(&mut self, claimant: &'static str) -> Result<Self::scl, $crate::registry::ClaimError> {
+                    $take!(self, $scl_port::$scl_pin, claimant)
+                }
+
+                fn d2(&mut self, claimant: &'static str) -> Result<Self::d2, $crate::registry::ClaimError> {
+                    $take!(self, $d2_port::$d2_pin, claimant)
+                }
+
+                fn d3(&mut self, claimant: &'static str) -> Result<Self::d3, $crate::registry::ClaimError> {
+                    $take!(self, $d3_port::$d3_pin, claimant)
+                }
+
+                fn d4(&mut self, claimant: &'static str) -> Result<Self::d4, $crate::registry::ClaimError> {
+                    $take!(self, $d4_port::$d4_pin, claimant)
+                }
+
+                fn d5(&mut self, claimant: &'static str) -> Result<Self::d5, $crate::registry::ClaimError> {
+                    $take!(self, $d5_port::$d5_pin, claimant)
+                }
+
+                fn d6(&mut self, claimant: &'static str) -> Result<Self::d6, $crate::registry::ClaimError> {
+                    $take!(self, $d6_port::$d6_pin, claimant)
+                }
+
+                fn d7(&mut self, claimant: &'static str) -> Result<Self::d7, $crate::registry::ClaimError> {
+                    $take!(self, $d7_port::$d7_pin, claimant)
+                }
+
+                fn d8(&mut self, claimant: &'static str) -> Result<Self::d8, $crate::registry::ClaimError> {
+                    $take!(self, $d8_port::$d8_pin, claimant)
+                }
+
+                fn p0(&mut self, claimant: &'static str) -> Result<Self::p0, $crate::registry::ClaimError> {
+                    $take!(self, $p0_port::$p0_pin, claimant)
+                }
+
+                fn tx(&mut self, claimant: &'static str) -> Result<Self::tx, $crate::registry::ClaimError> {
+                    $take!(self, $tx_port::$tx_pin, claimant)
+                }
+
+                fn rx(&mut self, claimant: &'static str) -> Result<Self::rx, $crate::registry::ClaimError> {
+                    $take!(self, $rx_port::$rx_pin, claimant)
+                }
+
+                fn miso(&mut self, claimant: &'static str) -> Result<Self::miso, $crate::registry::ClaimError> {
+                    $take!(self, $miso_port::$miso_pin, claimant)
+                }
+
+                fn mosi(&mut self, claimant: &'static str) -> Result<Self::mosi, $crate::registry::ClaimError> {
+                    $take!(self, $mosi_port::$mosi_pin, claimant)
+                }
+
+                fn sck(&mut self, claimant: &'static str) -> Result<Self::sck, $crate::registry::ClaimError> {
+                    $take!(self, $sck_port::$sck_pin, claimant)
+                }
+
+                fn a5(&mut self, claimant: &'static str) -> Result<Self::a5, $crate::registry::ClaimError> {
+                    $take!(self, $a5_port::$a5_pin, claimant)
+                }
+
+                fn a4(&mut self, claimant: &'static str) -> Result<Self::a4, $crate::registry::ClaimError> {
+                    $take!(self, $a4_port::$a4_pin, claimant)
+                }
+
+                fn a3(&mut self, claimant: &'static str) -> Result<Self::a3, $crate::registry::ClaimError> {
+                    $take!(self, $a3_port::$a3_pin, claimant)
+                }
+
+                fn a2(&mut self, claimant: &'static str) -> Result<Self::a2, $crate::registry::ClaimError> {
+                    $take!(self, $a2_port::$a2_pin, claimant)
+                }
+
+                fn a1(&mut self, claimant: &'static str) -> Result<Self::a1, $crate::registry::ClaimError> {
+                    $take!(self, $a1_port::$a1_pin, claimant)
+                }
+
+                fn a0(&mut self, claimant: &'static str) -> Result<Self::a0, $crate::registry::ClaimError> {
+                    $take!(self, $a0_port::$a0_pin, claimant)
+                }
+            }
+
+            impl ReleasePins for $platform {
+                fn sda(&mut self, pin: <Self as Pins>::sda) {
+                    $release!(self, $sda_port::$sda_pin, pin)
+                }
+
+                fn scl(&mut self, pin: <Self as Pins>::scl) {
+                    $release!(self, $scl_port::$scl_pin, pin)
+                }
+
+                fn d2(&mut self, pin: <Self as Pins>::d2) {
+                    $release!(self, $d2_port::$d2_pin, pin)
+                }
+
+                fn d3(&mut self, pin: <Self as Pins>::d3) {
+                    $release!(self, $d3_port::$d3_pin, pin)
+                }
+
+                fn d4(&mut self, pin: <Self as Pins>::d4) {
+                    $release!(self, $d4_port::$d4_pin, pin)
+                }
+
+                fn d5(&mut self, pin: <Self as Pins>::d5) {
+                    $release!(self, $d5_port::$d5_pin, pin)
+                }
+
+                fn d6(&mut self, pin: <Self as Pins>::d6) {
+                    $release!(self, $d6_port::$d6_pin, pin)
+                }
+
+                fn d7(&mut self, pin: <Self as Pins>::d7) {
+                    $release!(self, $d7_port::$d7_pin, pin)
+                }
+
+                fn d8(&mut self, pin: <Self as Pins>::d8) {
+                    $release!(self, $d8_port::$d8_pin, pin)
+                }
+
+                fn p0(&mut self, pin: <Self as Pins>::p0) {
+                    $release!(self, $p0_port::$p0_pin, pin)
+                }
+
+                fn tx(&mut self, pin: <Self as Pins>::tx) {
+                    $release!(self, $tx_port::$tx_pin, pin)
+                }
+
+                fn rx(&mut self, pin: <Self as Pins>::rx) {
+                    $release!(self, $rx_port::$rx_pin, pin)
+                }
+
+                fn miso(&mut self, pin: <Self as Pins>::miso) {
+                    $release!(self, $miso_port::$miso_pin, pin)
+                }
+
+                fn mosi(&mut self, pin: <Self as Pins>::mosi) {
+                    $release!(self, $mosi_port::$mosi_pin, pin)
+                }
+
+                fn sck(&mut self, pin: <Self as Pins>::sck) {
+                    $release!(self, $sck_port::$sck_pin, pin)
+                }
+
+                fn a5(&mut self, pin: <Self as Pins>::a5) {
+                    $release!(self, $a5_port::$a5_pin, pin)
+                }
+
+                fn a4(&mut self, pin: <Self as Pins>::a4) {
+                    $release!(self, $a4_port::$a4_pin, pin)
+                }
+
+                fn a3(&mut self, pin: <Self as Pins>::a3) {
+                    $release!(self, $a3_port::$a3_pin, pin)
+                }
+
+                fn a2(&mut self, pin: <Self as Pins>::a2) {
+                    $release!(self, $a2_port::$a2_pin, pin)
+                }
+
+                fn a1(&mut self, pin: <Self as Pins>::a1) {
+                    $release!(self, $a1_port::$a1_pin, pin)
+                }
+
+                fn a0(&mut self, pin: <Self as Pins>::a0) {
+                    $release!(self, $a0_port::$a0_pin, pin)
+                }
+            }
+
+            impl $crate::specs::feather::Feather for $platform {
+                type MainI2cMapping = $main_i2c_mapping;
+
+                type SDA = <Self as Pins>::sda;
+                type SCL = <Self as Pins>::scl;
+                type D2 = <Self as Pins>::d2;
+                type D3 = <Self as Pins>::d3;
+                type D4 = <Self as Pins>::d4;
+                type D5 = <Self as Pins>::d5;
+                type D6 = <Self as Pins>::d6;
+                type D7 = <Self as Pins>::d7;
+                type D8 = <Self as Pins>::d8;
+                type P0 = <Self as Pins>::p0;
+                type TX = <Self as Pins>::tx;
+                type RX = <Self as Pins>::rx;
+                type MISO = <Self as Pins>::miso;
+                type MOSI = <Self as Pins>::mosi;
+                type SCK = <Self as Pins>::sck;
+                type A5 = <Self as Pins>::a5;
+                type A4 = <Self as Pins>::a4;
+                type A3 = <Self as Pins>::a3;
+                type A2 = <Self as Pins>::a2;
+                type A1 = <Self as Pins>::a1;
+                type A0 = <Self as Pins>::a0;
+
+                $crate::feather_board!(@main_led $take, $release, [$($main_led_polarity)?] $main_led_kind $main_led $($main_led_pin $main_led_ty)?);
+
+                fn try_take_main_i2c(
+                    &mut self,
+                ) -> Result<$crate::specs::feather::MainI2cBus<Self>, $crate::registry::ClaimError> {
+                    <$platform>::$main_i2c(self)
+                }
+
+                fn try_take_sda(&mut self) -> Result<Self::SDA, $crate::registry::ClaimError> {
+                    Pins::sda(self, "sda")
+                }
+
+                fn try_take_scl(&mut self) -> Result<Self::SCL, $crate::registry::ClaimError> {
+                    Pins::scl(self, "scl")
+                }
+
+                fn try_take_d2(&mut self) -> Result<Self::D2, $crate::registry::ClaimError> {
+                    Pins::d2(self, "d2")
+                }
+
+                fn try_take_d3(&mut self) -> Result<Self::D3, $crate::registry::ClaimError> {
+                    Pins::d3(self, "d3")
+                }
+
+                fn try_take_d4(&mut self) -> Result<Self::D4, $crate::registry::ClaimError> {
+                    Pins::d4(self, "d4")
+                }
+
+                fn try_take_d5(&mut self) -> Result<Self::D5, $crate::registry::ClaimError> {
+                    Pins::d5(self, "d5")
+                }
+
+                fn try_take_d6(&mut self) -> Result<Self::D6, $crate::registry::ClaimError> {
+                    Pins::d6(self, "d6")
+                }
+
+                fn try_take_d7(&mut self) -> Result<Self::D7, $crate::registry::ClaimError> {
+                    Pins::d7(self, "d7")
+                }
+
+                fn try_take_d8(&mut self) -> Result<Self::D8, $crate::registry::ClaimError> {
+                    Pins::d8(self, "d8")
+                }
+
+                fn try_take_p0(&mut self) -> Result<Self::P0, $crate::registry::ClaimError> {
+                    Pins::p0(self, "p0")
+                }
+
+                fn try_take_tx(&mut self) -> Result<Self::TX, $crate::registry::ClaimError> {
+                    Pins::tx(self, "tx")
+                }
+
+                fn try_take_rx(&mut self) -> Result<Self::RX, $crate::registry::ClaimError> {
+                    Pins::rx(self, "rx")
+                }
+
+                fn try_take_miso(&mut self) -> Result<Self::MISO, $crate::registry::ClaimError> {
+                    Pins::miso(self, "miso")
+                }
+
+                fn try_take_mosi(&mut self) -> Result<Self::MOSI, $crate::registry::ClaimError> {
+                    Pins::mosi(self, "mosi")
+                }
+
+                fn try_take_sck(&mut self) -> Result<Self::SCK, $crate::registry::ClaimError> {
+                    Pins::sck(self, "sck")
+                }
+
+                fn try_take_a5(&mut self) -> Result<Self::A5, $crate::registry::ClaimError> {
+                    Pins::a5(self, "a5")
+                }
+
+                fn try_take_a4(&mut self) -> Result<Self::A4, $crate::registry::ClaimError> {
+                    Pins::a4(self, "a4")
+                }
+
+                fn try_take_a3(&mut self) -> Result<Self::A3, $crate::registry::ClaimError> {
+                    Pins::a3(self, "a3")
+                }
+
+                fn try_take_a2(&mut self) -> Result<Self::A2, $crate::registry::ClaimError> {
+                    Pins::a2(self, "a2")
+                }
+
+                fn try_take_a1(&mut self) -> Result<Self::A1, $crate::registry::ClaimError> {
+                    Pins::a1(self, "a1")
+                }
+
+                fn try_take_a0(&mut self) -> Result<Self::A0, $crate::registry::ClaimError> {
+                    Pins::a0(self, "a0")
+                }
+
+                fn release_sda(&mut self, sda: Self::SDA) {
+                    ReleasePins::sda(self, sda)
+                }
+
+                fn release_scl(&mut self, scl: Self::SCL) {
+                    ReleasePins::scl(self, scl)
+                }
+
+                fn release_d2(&mut self, d2: Self::D2) {
+                    ReleasePins::d2(self, d2)
+                }
+
+                fn release_d3(&mut self, d3: Self::D3) {
+                    ReleasePins::d3(self, d3)
+                }
+
+                fn release_d4(&mut self, d4: Self::D4) {
+                    ReleasePins::d4(self, d4)
+                }
+
+                fn release_d5(&mut self, d5: Self::D5) {
+                    ReleasePins::d5(self, d5)
+                }
+
+                fn release_d6(&mut self, d6: Self::D6) {
+                    ReleasePins::d6(self, d6)
+                }
+
+                fn release_d7(&mut self, d7: Self::D7) {
+                    ReleasePins::d7(self, d7)
+                }
+
+                fn release_d8(&mut self, d8: Self::D8) {
+                    ReleasePins::d8(self, d8)
+                }
+
+                fn release_p0(&mut self, p0: Self::P0) {
+                    ReleasePins::p0(self, p0)
+                }
+
+                fn release_tx(&mut self, tx: Self::TX) {
+                    ReleasePins::tx(self, tx)
+                }
+
+                fn release_rx(&mut self, rx: Self::RX) {
+                    ReleasePins::rx(self, rx)
+                }
+
+                fn release_miso(&mut self, miso: Self::MISO) {
+                    ReleasePins::miso(self, miso)
+                }
+
+                fn release_mosi(&mut self, mosi: Self::MOSI) {
+                    ReleasePins::mosi(self, mosi)
+                }
+
+                fn release_sck(&mut self, sck: Self::SCK) {
+                    ReleasePins::sck(self, sck)
+                }
+
+                fn release_a5(&mut self, a5: Self::A5) {
+                    ReleasePins::a5(self, a5)
+                }
+
+                fn release_a4(&mut self, a4: Self::A4) {
+                    ReleasePins::a4(self, a4)
+                }
+
+                fn release_a3(&mut self, a3: Self::A3) {
+                    ReleasePins::a3(self, a3)
+                }
+
+                fn release_a2(&mut self, a2: Self::A2) {
+                    ReleasePins::a2(self, a2)
+                }
+
+                fn release_a1(&mut self, a1: Self::A1) {
+                    ReleasePins::a1(self, a1)
+                }
+
+                fn release_a0(&mut self, a0: Self::A0) {
+                    ReleasePins::a0(self, a0)
+                }
+            }
+        };
+    };
+    (@main_led $take:ident, $release:ident, $polarity:tt => $alias:ident) => {
+        $crate::feather_board!(@polarity_check $polarity);
+
+        type MainLed = $crate::feather_board!(@polarity_ty $polarity, <Self as Pins>::$alias);
+
+        fn try_take_main_led(&mut self) -> Result<Self::MainLed, $crate::registry::ClaimError> {
+            $crate::feather_board!(@polarity_take $polarity, Pins::$alias(self, "main_led"))
+        }
+
+        fn release_main_led(&mut self, main_led: Self::MainLed) {
+            ReleasePins::$alias(self, $crate::feather_board!(@polarity_release $polarity, main_led))
+        }
+    };
+    (@main_led $take:ident, $release:ident, $polarity:tt = $port:ident $pin:ident $ty:ty) => {
+        $crate::feather_board!(@polarity_check $polarity);
+
+        type MainLed = $crate::feather_board!(@polarity_ty $polarity, $ty);
+
+        fn try_take_main_led(&mut self) -> Result<Self::MainLed, $crate::registry::ClaimError> {
+            $crate::feather_board!(@polarity_take $polarity, $take!(self, $port::$pin, "main_led"))
+        }
+
+        fn release_main_led(&mut self, main_led: Self::MainLed) {
+            $release!(self, $port::$pin, $crate::feather_board!(@polarity_release $polarity, main_led))
+        }
+    };
+    (@polarity_ty [$(active_high)?], $ty:ty) => { $ty };
+    (@polarity_ty [active_low], $ty:ty) => { $crate::gpio::Inverted<$ty> };
+    (@polarity_take [$(active_high)?], $pin:expr) => { $pin };
+    (@polarity_take [active_low], $pin:expr) => { $pin.map($crate::gpio::Inverted::new) };
+    (@polarity_release [$(active_high)?], $pin:expr) => { $pin };
+    (@polarity_release [active_low], $pin:expr) => { $pin.into_inner() };
+    (@polarity_check [$(active_high)?]) => {};
+    (@polarity_check [active_low]) => {};
+    (@polarity_check [$polarity:tt]) => {
+        ::core::compile_error!(::core::concat!(
+            "unknown main LED polarity `",
+            ::core::stringify!($polarity),
+            "`, expected `active_low` or `active_high`",
+        ));
+    };
+    // Unknown polarities are reported once by `@polarity_check`, so treat them as active high to
+    // avoid follow-up errors.
+    (@polarity_ty [$polarity:tt], $ty:ty) => { $ty };
+    (@polarity_take [$polarity:tt], $pin:expr) => { $pin };
+    (@polarity_release [$polarity:tt], $pin:expr) => { $pin };
 }
@@ -1,3 +1,4 @@
+use crate::io;
 use crate::time;
 use core::fmt;
 use core::pin;
@@ -44,7 +45,52 @@ pub trait TimerExt: Timer {
     }
 }
 
-impl<T> TimerExt for T where T: Timer {}
+impl<T> TimerExt for T where T: Timer + ?Sized {}
+
+/// An object-safe version of [`Timer`], with [`io::ErrorKind`] as its fixed error type.
+///
+/// This is implemented for every [`Timer`] that is `Unpin` and whose errors convert into
+/// [`io::ErrorKind`], and `dyn DynTimer` implements [`Timer`] in turn, so timers of different
+/// types can be used through `&mut dyn DynTimer`.
+pub trait DynTimer: fmt::Debug + Unpin {
+    /// Polls the start of this timer, like [`Timer::poll_start`].
+    fn poll_start(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), io::ErrorKind>>;
+
+    /// Polls the next tick of this timer, like [`Timer::poll_tick`].
+    fn poll_tick(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), io::ErrorKind>>;
+}
+
+impl<T> DynTimer for T
+where
+    T: Timer + Unpin,
+    T::Error: Into<io::ErrorKind>,
+{
+    fn poll_start(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), io::ErrorKind>> {
+        Timer::poll_start(pin::Pin::new(self), cx).map_err(Into::into)
+    }
+
+    fn poll_tick(&mut self, cx: &mut task::Context<'_>) -> task::Poll<Result<(), io::ErrorKind>> {
+        Timer::poll_tick(pin::Pin::new(self), cx).map_err(Into::into)
+    }
+}
+
+impl Timer for dyn DynTimer + '_ {
+    type Error = io::ErrorKind;
+
+    fn poll_start(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        DynTimer::poll_start(self.get_mut(), cx)
+    }
+
+    fn poll_tick(
+        self: pin::Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Result<(), Self::Error>> {
+        DynTimer::poll_tick(self.get_mut(), cx)
+    }
+}
 
 pub trait IntoPeriodicTimer: Timer {
     type PeriodicTimer: Timer<Error = Self::Error> + Unpin;